@@ -0,0 +1,71 @@
+use macroquad::{
+    prelude::{vec2, Image, Rect, Texture2D, BLUE, ORANGE, WHITE},
+    texture::FilterMode,
+};
+use monad_quad::{
+    components::{
+        asyncs::{AsyncComp, AsyncState, OnLoaded},
+        logic::StateFull,
+        render::{Sprite, SpriteProps},
+        Context,
+    },
+    Component,
+};
+
+struct MainState {
+    texture: AsyncState<Texture2D>,
+    rotation: f32,
+}
+
+/// a checkerboard standing in for a texture that gets loaded from disk with [macroquad::texture::load_texture]
+async fn load_checkerboard() -> Texture2D {
+    let mut image = Image::gen_image_color(8, 8, WHITE);
+    for x in 0..8 {
+        for y in 0..8 {
+            image.set_pixel(x, y, if (x + y) % 2 == 0 { ORANGE } else { BLUE });
+        }
+    }
+    let texture = Texture2D::from_image(&image);
+    texture.set_filter(FilterMode::Nearest);
+    texture
+}
+
+struct Spin;
+
+impl Component<&MainState, &mut MainState> for Spin {
+    type Input = ();
+
+    fn instantiate(_: Self::Input) -> Self {
+        Self
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut MainState) -> &'c mut MainState {
+        state.rotation += context.get_delta();
+        state
+    }
+}
+
+#[macroquad::main("Sprite")]
+async fn main() {
+    let state = MainState {
+        texture: AsyncState::new_loading(load_checkerboard()),
+        rotation: 0.,
+    };
+    StateFull::new_from(state)
+        .render((
+            Spin,
+            OnLoaded::instantiate((
+                |v: &MainState| v.texture.clone(),
+                AsyncComp::<Texture2D, SpriteProps, Sprite>::map_in(|texture, _, v: &MainState| {
+                    SpriteProps::new(texture.borrow().clone())
+                        .with_source(Rect::new(0., 0., 4., 4.))
+                        .with_position(vec2(100., 100.))
+                        .with_size(vec2(128., 128.))
+                        .with_rotation(v.rotation)
+                        .with_flip(true, false)
+                })
+                .map_out(|_, _| {}),
+            )),
+        ))
+        .await;
+}
@@ -1,38 +1,32 @@
-use macroquad::prelude::{vec2, GREEN};
+use macroquad::prelude::{vec2, Vec2, GREEN};
 use monad_quad::components::{
-    animation::{Tween, TweenConfig, TweenKind},
+    animation::{TweenField, TweenKind, Tweened},
     logic::{Comp, StateFull},
     render::{Rectangle, RectangleProps},
 };
 
 struct MainState {
-    tween_state: TweenConfig<f32>,
+    location: Vec2,
+    tween_state: Tweened<Vec2>,
 }
 
 #[macroquad::main("Sample game")]
 async fn main() {
     let state = MainState {
-        tween_state: TweenConfig {
-            tween_data: 0.,
-            at_time: 0.,
-            time_in_seconds: 2.,
-            tween_kind: TweenKind::EaseInBounce,
-            run: true,
-        },
+        location: vec2(0., 40.),
+        tween_state: Tweened::new(vec2(0., 40.), vec2(250., 40.), 2., TweenKind::EaseInBounce),
     };
     StateFull::new_from(state)
         .render((
             Comp::<_, Rectangle>::map_in(|v: &MainState| RectangleProps {
                 size: vec2(20., 20.),
                 color: GREEN,
-                location: vec2(v.tween_state.tween_data * 250., 40.),
+                location: v.location,
             })
             .map_out(|_, _| {}),
-            Comp::<_, Tween<_>>::map_in(|v: &MainState| v.tween_state.to_owned()).map_out_with(
-                |y, x| {
-                    *x = y;
-                },
-                |x, y| y.tween_state = x,
+            TweenField::new(
+                |v: &mut MainState| &mut v.tween_state,
+                |location, v: &mut MainState| v.location = location,
             ),
         ))
         .await;
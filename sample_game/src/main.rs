@@ -4,62 +4,72 @@ mod settings;
 
 use macroquad::prelude::*;
 
-use main_menu::{main_menu2, MainMenuProperties, SwitchingTo};
+use main_menu::{main_menu2, MainMenuProperties, OnScreen};
 use monad_quad::components::{
-    events::{Event, KeyDown},
-    logic::{Choice, Comp, OnlyRenderWith, StateFull, StaticValue},
+    events::{ActionListener, EventListener, EventQueue, KeyPressed, TimerState},
+    logic::{Comp, ForEachProperties, LoopMode, OnlyRenderWith, StateFull, StaticValue},
     render::{Background, Text, TextProperties, Viewport},
+    scenes::SceneStack,
 };
+use monad_quad::switch;
 use nodes::{
-    CoinProperties, Coins, ControlProps, Enemies, EnemyProperties, ItemRendererProperties, Player,
-    PlayerProps, ScoreDisplay, ScoreDisplayProperties,
+    CoinProperties, Coins, ControlProps, Enemies, EnemyProperties, GameEvent, Player, PlayerProps,
+    ScoreDisplay, ScoreDisplayProperties, SpawnedItem,
 };
 use settings::Settings;
 
+enum Screen {
+    MainMenu,
+    Playing,
+    GameOver,
+}
+
 struct MainState {
     started_game: bool,
     player_state: PlayerProps,
-    coins: Vec<Vec2>,
+    coins: Vec<SpawnedItem>,
     coin_size: f32,
     coin_speed: f32,
     coin_spawn_chance: f32,
+    coin_spawn_timer: TimerState,
     enemy_size: f32,
     enemy_spawn_chance: f32,
-    enemies: Vec<Vec2>,
+    enemy_spawn_timer: TimerState,
+    enemies: Vec<SpawnedItem>,
     enemy_speed: f32,
     paused: bool,
-    switching: SwitchingTo,
+    events: EventQueue<GameEvent>,
+    menu: SceneStack<OnScreen>,
     settings: Settings,
 }
 impl MainState {
     pub fn new_with_settings(settings: Settings) -> Self {
         MainState {
-            switching: SwitchingTo::OnScreen(main_menu::OnScreen::MainMenu),
+            menu: SceneStack::new(OnScreen::MainMenu),
             started_game: false,
 
             paused: false,
+            events: EventQueue::new(),
             player_state: PlayerProps {
                 lives: 3,
                 size: vec2(10., 15.),
                 score: 0,
                 location: vec2(screen_width() / 2., screen_height() / 2.),
                 control: ControlProps {
-                    left: KeyCode::Left,
-                    right: KeyCode::Right,
-                    up: KeyCode::Up,
-                    down: KeyCode::Down,
-                    speed: 100.,
+                    bindings: settings.controls.clone(),
                     dir: vec2(0., 0.),
                 },
             },
 
             coin_size: 30.,
             coin_spawn_chance: 0.02,
+            coin_spawn_timer: TimerState::forever(1. / 60.),
             coin_speed: 30.,
             coins: Vec::new(),
 
             enemy_size: 25.,
             enemy_spawn_chance: 0.025,
+            enemy_spawn_timer: TimerState::forever(1. / 60.),
             enemy_speed: 35.,
             enemies: Vec::new(),
             settings,
@@ -69,145 +79,156 @@ impl MainState {
         let settings = Settings::read_from_settings_or_default().await;
         Self::new_with_settings(settings)
     }
+    fn screen(&self) -> Screen {
+        if !self.started_game {
+            Screen::MainMenu
+        } else if self.player_state.lives > 0 {
+            Screen::Playing
+        } else {
+            Screen::GameOver
+        }
+    }
     pub fn set_settings(&mut self, settings: Settings) {
-        self.settings.apply_new_settings(settings)
+        self.settings.apply_new_settings(settings);
+        self.player_state.control.bindings = self.settings.controls.clone();
     }
 }
 #[macroquad::main("Sample game")]
 async fn main() {
     let state = MainState::new().await;
 
-    let mut state = StateFull::new_from(state);
+    let state = StateFull::new_from(state).with_loop_mode(LoopMode::fixed(60.));
 
     let scene_tree = Viewport::new::<MainState>(
         vec2(1920., 1080.),
         (
             StaticValue::new(BLACK, Background),
-            Choice::new(
-                |x: &MainState| !x.started_game,
-                Comp::map_in(|v: &MainState| MainMenuProperties {
-                    switching: v.switching.to_owned(),
+            switch!(|state: &MainState| state.screen(), {
+                Screen::MainMenu => Comp::map_in(|v: &MainState| MainMenuProperties {
+                    menu: v.menu.to_owned(),
                     selected_button: None,
                     started_game: v.started_game,
-                    settings: v.settings,
+                    settings: v.settings.clone(),
                 })
                 .map_out_for(
                     |props, state| {
                         state.started_game = props.started_game;
-                        state.switching = props.switching;
+                        state.menu = props.menu;
                         state.set_settings(props.settings);
                     },
                     main_menu2(),
                 ),
-                Choice::new(
-                    |x| x.player_state.lives > 0,
-                    (
-                        Event::new(
-                            |_: &MainState| is_key_pressed(KeyCode::P),
-                            |v, state| {
-                                if v {
-                                    state.paused = !state.paused
-                                }
-                            },
-                        ),
-                        OnlyRenderWith::new(
-                            |v: &MainState| !v.paused,
-                            (
-                                Comp::<_, Player>::map_in(|state: &MainState| {
-                                    state.player_state.to_owned()
-                                })
-                                .map_out(|new_state, state| state.player_state = new_state),
-                                Comp::<_, Coins>::map_in(|state: &MainState| {
-                                    ItemRendererProperties {
-                                        items: state.coins.to_owned(),
-                                        extra_data: CoinProperties {
-                                            coin_size: state.coin_size,
-                                            coin_speed: state.coin_speed,
-                                            spawn_chance: state.coin_spawn_chance,
-                                            player_loc: state.player_state.location,
-                                            player_size: state.player_state.size,
-                                            touched_coins: 0,
-                                            coins_let_through: 0,
-                                        },
-                                    }
-                                })
-                                .map_out(|new_state, state| {
-                                    state.coins = new_state.items;
-                                    state.player_state.score += (new_state.extra_data.touched_coins
-                                        * 10)
-                                        - (new_state.extra_data.coins_let_through * 15)
-                                }),
-                                Comp::<_, Enemies>::map_in(|state: &MainState| {
-                                    ItemRendererProperties {
-                                        items: state.enemies.to_owned(),
-                                        extra_data: EnemyProperties {
-                                            enemies_size: state.enemy_size,
-                                            enemies_color: RED,
-                                            enemies_speed: state.enemy_speed,
-                                            enemies_chance: state.enemy_spawn_chance,
-                                            player_loc: state.player_state.location,
-                                            player_size: state.player_state.size,
-                                            got_hit: false,
-                                            reached_the_end: 0,
-                                        },
-                                    }
-                                })
-                                .map_out(|new_state, state| {
-                                    state.enemies = new_state.items;
-                                    state.player_state.score +=
-                                        new_state.extra_data.reached_the_end * 11;
-                                    if new_state.extra_data.got_hit {
-                                        state.player_state.lives -= 1;
-                                    }
-                                }),
-                                Comp::<_, ScoreDisplay>::map_in(|state: &MainState| {
-                                    ScoreDisplayProperties {
-                                        score: state.player_state.score,
-                                        location: vec2(10., 10.),
-                                        font_size: 14.,
-                                        color: GREEN,
-                                        lives: state.player_state.lives,
-                                        lives_size: 14.,
-                                        lives_location: vec2(10., 15.),
+                Screen::Playing => (
+                    ActionListener::new(
+                        settings::PAUSE,
+                        |state: &MainState| &state.settings.controls,
+                        |action, state| {
+                            if action.pressed {
+                                state.paused = !state.paused
+                            }
+                        },
+                    ),
+                    OnlyRenderWith::new(
+                        |v: &MainState| !v.paused,
+                        (
+                            Comp::<_, Player>::map_in(|state: &MainState| {
+                                state.player_state.to_owned()
+                            })
+                            .map_out(|new_state, state| state.player_state = new_state),
+                            Comp::<_, Coins>::map_in(|state: &MainState| ForEachProperties {
+                                items: state.coins.to_owned(),
+                                shared: CoinProperties {
+                                    coin_size: state.coin_size,
+                                    coin_speed: state.coin_speed,
+                                    spawn_chance: state.coin_spawn_chance,
+                                    player_loc: state.player_state.location,
+                                    player_size: state.player_state.size,
+                                    events: state.events.to_owned(),
+                                    spawn_timer: state.coin_spawn_timer.to_owned(),
+                                },
+                            })
+                            .map_out(|new_state, state| {
+                                state.coins = new_state.items;
+                                state.events = new_state.shared.events;
+                                state.coin_spawn_timer = new_state.shared.spawn_timer;
+                            }),
+                            Comp::<_, Enemies>::map_in(|state: &MainState| ForEachProperties {
+                                items: state.enemies.to_owned(),
+                                shared: EnemyProperties {
+                                    enemies_size: state.enemy_size,
+                                    enemies_color: RED,
+                                    enemies_speed: state.enemy_speed,
+                                    enemies_chance: state.enemy_spawn_chance,
+                                    player_loc: state.player_state.location,
+                                    player_size: state.player_state.size,
+                                    events: state.events.to_owned(),
+                                    spawn_timer: state.enemy_spawn_timer.to_owned(),
+                                },
+                            })
+                            .map_out(|new_state, state| {
+                                state.enemies = new_state.items;
+                                state.events = new_state.shared.events;
+                                state.enemy_spawn_timer = new_state.shared.spawn_timer;
+                            }),
+                            EventListener::new(
+                                |state: &mut MainState| &mut state.events,
+                                |event, state| match event {
+                                    GameEvent::CoinTouched => state.player_state.score += 10,
+                                    GameEvent::CoinLetThrough => state.player_state.score -= 15,
+                                    GameEvent::EnemyHitPlayer => state.player_state.lives -= 1,
+                                    GameEvent::EnemyReachedTheEnd => {
+                                        state.player_state.score += 11
                                     }
-                                })
-                                .map_out(|_, _| {}),
-                            ),
-                            StaticValue::new(
-                                TextProperties {
-                                    text: "Paused".to_string(),
-                                    location: vec2(100., 100.),
-                                    font_size: 20.,
-                                    color: GREEN,
                                 },
-                                Text,
                             ),
-                        ),
-                    ),
-                    (
-                        KeyDown::new(
-                            |_: &MainState| KeyCode::Enter,
-                            |v, state| {
-                                if v {
-                                    let mut new_state =
-                                        MainState::new_with_settings(state.settings);
-                                    new_state.started_game = true;
-                                    *state = new_state;
+                            Comp::<_, ScoreDisplay>::map_in(|state: &MainState| {
+                                ScoreDisplayProperties {
+                                    score: state.player_state.score,
+                                    location: vec2(10., 10.),
+                                    font_size: 14.,
+                                    color: GREEN,
+                                    lives: state.player_state.lives,
+                                    lives_size: 14.,
+                                    lives_location: vec2(10., 15.),
                                 }
-                            },
+                            })
+                            .map_out(|_, _| {}),
                         ),
                         StaticValue::new(
                             TextProperties {
-                                text: "Game Over".to_string(),
-                                location: vec2(10., 20.),
-                                font_size: 40.,
-                                color: RED,
+                                text: "Paused".to_string(),
+                                location: vec2(100., 100.),
+                                font_size: 20.,
+                                color: GREEN,
                             },
                             Text,
                         ),
                     ),
                 ),
-            ),
+                Screen::GameOver => (
+                    KeyPressed::new(
+                        |_: &MainState| KeyCode::Enter,
+                        |pressed, state| {
+                            if !pressed {
+                                return;
+                            }
+                            let mut new_state =
+                                MainState::new_with_settings(state.settings.clone());
+                            new_state.started_game = true;
+                            *state = new_state;
+                        },
+                    ),
+                    StaticValue::new(
+                        TextProperties {
+                            text: "Game Over".to_string(),
+                            location: vec2(10., 20.),
+                            font_size: 40.,
+                            color: RED,
+                        },
+                        Text,
+                    ),
+                ),
+            }),
         ),
     );
     state.render(scene_tree).await;
@@ -1,6 +1,6 @@
-use macroquad::{
-    prelude::{vec2, Vec2},
-    window::screen_width,
+use monad_quad::components::{
+    animation::TweenKind,
+    scenes::{SceneStack, SlideDirection, Transition},
 };
 
 use crate::settings::Settings;
@@ -11,39 +11,17 @@ pub enum OnScreen {
     Settings,
 }
 
-#[derive(Clone, Debug)]
-pub enum SwitchingTo {
-    OnScreen(OnScreen),
-    Switch {
-        from: OnScreen,
-        to: OnScreen,
-        full_progress: f32,
-        at: f32,
-    },
-}
-
-impl SwitchingTo {
-    pub fn get_progress_from(&self, from: OnScreen) -> Option<f32> {
+impl OnScreen {
+    /// the child of the menu router that shows this screen
+    pub fn index(&self) -> usize {
         match self {
-            SwitchingTo::Switch {
-                from: from_2,
-                full_progress,
-                ..
-            } if from == *from_2 => Some(*full_progress),
-            SwitchingTo::Switch {
-                to, full_progress, ..
-            } if from == *to => Some(-full_progress),
-            _ => None,
+            OnScreen::MainMenu => 0,
+            OnScreen::Settings => 1,
         }
     }
-    pub fn get_location_of_window(&self, from: OnScreen) -> Vec2 {
-        let progress = self.get_progress_from(from).unwrap_or(0.);
-        let loc = if progress.is_sign_negative() {
-            screen_width() - ((-progress) * screen_width())
-        } else {
-            progress * screen_width()
-        };
-        vec2(loc, 0.)
+    /// the settings slide in from the right, and slide back out that way once they get popped
+    pub fn transition() -> Transition {
+        Transition::slide(SlideDirection::Right, 2., TweenKind::EaseInOutExpo)
     }
 }
 
@@ -56,7 +34,7 @@ pub enum MainMenuButtonSelected {
 #[derive(Clone, Debug)]
 pub struct MainMenuProperties {
     pub started_game: bool,
-    pub switching: SwitchingTo,
+    pub menu: SceneStack<OnScreen>,
     pub selected_button: Option<MainMenuButtonSelected>,
     pub settings: Settings,
 }
@@ -64,7 +42,7 @@ impl MainMenuProperties {
     pub fn new() -> Self {
         Self {
             started_game: false,
-            switching: SwitchingTo::OnScreen(OnScreen::MainMenu),
+            menu: SceneStack::new(OnScreen::MainMenu),
             selected_button: None,
             settings: Default::default(),
         }
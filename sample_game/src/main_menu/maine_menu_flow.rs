@@ -7,14 +7,14 @@ use monad_quad::components::ui::{ButtonProperties, WindowProperties};
 use super::{
     main_menu_state::MainMenuButtonSelected,
     settings_menu::{self, SettingsMenuProperties},
-    MainMenuProperties, OnScreen, SwitchingTo,
+    MainMenuProperties, OnScreen,
 };
 
 impl MainMenuProperties {
     pub fn to_top_window_state(&self) -> WindowProperties<MainMenuProperties> {
-        let loc = self.switching.get_location_of_window(OnScreen::MainMenu);
+        // the router moves the window while switching screens
         WindowProperties {
-            location: loc,
+            location: vec2(0., 0.),
             size: vec2(screen_width(), screen_height()),
             label: Some("Monad Quad SAMPLE!".into()),
             title_bar: true,
@@ -43,7 +43,7 @@ impl MainMenuProperties {
         //me.start_game_button_selected = from.selected;
         me.started_game = from.extra_data;
     }
-    pub fn to_settings_menu_button_properties<'a>(&self) -> ButtonProperties<'a, SwitchingTo> {
+    pub fn to_settings_menu_button_properties<'a>(&self) -> ButtonProperties<'a, bool> {
         ButtonProperties {
             size: vec2(100., 50.),
             selected: self
@@ -52,22 +52,26 @@ impl MainMenuProperties {
                 .map(|v| *v == MainMenuButtonSelected::Settings)
                 .unwrap_or(false),
             position: vec2(screen_width() / 2. - 50., screen_height() / 2. + 30.),
-            extra_data: self.switching.clone(),
+            extra_data: false,
             content: "Settings!".into(),
         }
     }
-    pub fn merge_to_settings_menu_button(from: ButtonProperties<SwitchingTo>, me: &mut Self) {
-        me.switching = from.extra_data;
+    pub fn merge_to_settings_menu_button(from: ButtonProperties<bool>, me: &mut Self) {
+        if from.extra_data {
+            me.menu.push(OnScreen::Settings, OnScreen::transition());
+        }
     }
     pub fn to_settings_menu_properties(&self) -> settings_menu::SettingsMenuProperties {
         SettingsMenuProperties {
-            settings: self.settings,
-            switch: self.switching.to_owned(),
+            settings: self.settings.clone(),
+            go_back: false,
             has_back_button_selected: false,
         }
     }
     pub fn merge_from_settings_menu_properties(settings: SettingsMenuProperties, me: &mut Self) {
         me.settings = settings.settings;
-        me.switching = settings.switch;
+        if settings.go_back {
+            me.menu.pop(OnScreen::transition());
+        }
     }
 }
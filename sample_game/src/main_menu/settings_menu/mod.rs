@@ -11,7 +11,7 @@ use monad_quad::{
     Component,
 };
 
-use super::{MainMenuProperties, OnScreen, SwitchingTo};
+use super::MainMenuProperties;
 
 pub fn draw_settings_menu(
 ) -> impl for<'a> Component<&'a MainMenuProperties, &'a mut MainMenuProperties> {
@@ -25,12 +25,7 @@ pub fn draw_settings_menu(
                     Comp::<_, _>::map_in(SettingsMenuProperties::to_back_menu_props).map_out_for(
                         SettingsMenuProperties::merge_back_menu_props,
                         Button::instantiate(|v| {
-                            v.extra_data = SwitchingTo::Switch {
-                                from: OnScreen::Settings,
-                                to: OnScreen::MainMenu,
-                                full_progress: 0.0,
-                                at: 0.,
-                            };
+                            v.extra_data = true;
                         }),
                     ),
                     Comp::<_, ComboBox>::map_in(SettingsMenuProperties::to_screen_size_props)
@@ -6,23 +6,21 @@ use monad_quad::components::ui::{
     ButtonProperties, CheckboxProperties, ComboBoxProperties, WindowProperties,
 };
 
-use crate::{
-    main_menu::{OnScreen, SwitchingTo},
-    settings::{Settings, RESOLUTIONS},
-};
+use crate::settings::{Settings, RESOLUTIONS};
 
 #[derive(Clone)]
 pub struct SettingsMenuProperties {
     pub settings: Settings,
-    pub switch: SwitchingTo,
+    /// set once the back button got clicked
+    pub go_back: bool,
     pub has_back_button_selected: bool,
 }
 
 impl SettingsMenuProperties {
     pub fn to_top_window_props(&self) -> WindowProperties<Self> {
-        let location = self.switch.get_location_of_window(OnScreen::Settings);
+        // the router moves the window while switching screens
         WindowProperties {
-            location,
+            location: vec2(0., 0.),
             size: vec2(screen_width(), screen_height()),
             label: Some("Settings".into()),
             title_bar: true,
@@ -34,17 +32,17 @@ impl SettingsMenuProperties {
     pub fn merge_from_top_window_props(props: WindowProperties<Self>, me: &mut Self) {
         *me = props.extra_data;
     }
-    pub fn to_back_menu_props<'a>(&self) -> ButtonProperties<'a, SwitchingTo> {
+    pub fn to_back_menu_props<'a>(&self) -> ButtonProperties<'a, bool> {
         ButtonProperties {
             size: vec2(100., 50.),
             selected: self.has_back_button_selected,
             position: vec2(screen_width() / 2. - 50., screen_height() / 2. + 30.),
-            extra_data: self.switch.clone(),
+            extra_data: self.go_back,
             content: "Settings!".into(),
         }
     }
-    pub fn merge_back_menu_props(merge: ButtonProperties<SwitchingTo>, me: &mut Self) {
-        me.switch = merge.extra_data;
+    pub fn merge_back_menu_props(merge: ButtonProperties<bool>, me: &mut Self) {
+        me.go_back = merge.extra_data;
     }
     pub fn to_full_screen_button(&self) -> CheckboxProperties {
         CheckboxProperties {
@@ -5,20 +5,22 @@ use macroquad::{
     rand::RandomRange,
 };
 use monad_quad::components::{
-    events::VariableTimer,
+    events::{EventQueue, StateTimer, TimerState},
+    logic::{ForEach, ForEachProperties, ItemProperties},
     render::{Rectangle, RectangleProps},
     Component, Context,
 };
 
+use super::GameEvent;
+
 pub struct SpawnerConfig<ExtraData, T> {
-    pub max_time: f32,
-    pub size: Vec2,
+    pub timer: TimerState,
     pub extra_data: ExtraData,
     pub spawned_items: Vec<T>,
 }
 
 type ChildTimer<ExtraData, Item, Spawn> =
-    VariableTimer<fn(&SpawnerConfig<ExtraData, Item>) -> f32, Spawn>;
+    StateTimer<fn(&mut SpawnerConfig<ExtraData, Item>) -> &mut TimerState, Spawn>;
 
 pub struct Spawner<Item, ExtraData, Spawn: Fn(&Context, &mut SpawnerConfig<ExtraData, Item>)> {
     timer: ChildTimer<ExtraData, Item, Spawn>,
@@ -42,11 +44,13 @@ impl<Item, ExtraData, Spawn: Fn(&Context, &mut SpawnerConfig<ExtraData, Item>)>
     where
         Self: Sized,
     {
-        fn get_max_time<ExtraData, Item>(state: &SpawnerConfig<ExtraData, Item>) -> f32 {
-            state.max_time
+        fn get_timer<ExtraData, Item>(
+            state: &mut SpawnerConfig<ExtraData, Item>,
+        ) -> &mut TimerState {
+            &mut state.timer
         }
         Self {
-            timer: VariableTimer::new(get_max_time, input),
+            timer: StateTimer::new(get_timer, input),
             _spawner: PhantomData,
         }
     }
@@ -83,96 +87,28 @@ impl<Item, ExtraData, Spawn: Fn(&Context, &mut SpawnerConfig<ExtraData, Item>)>
     }
 }
 
-pub struct ItemRendererProperties<T: Clone> {
-    pub items: Vec<Vec2>,
-    pub extra_data: T,
-}
-
-pub struct ChildProperties<T> {
-    pub extra_data: T,
+/// Something that got spawned by a [Spawner]
+#[derive(Debug, Clone)]
+pub struct SpawnedItem {
+    /// unique among the items that currently exist, so [ForEach] can keep track of them
+    pub id: u64,
     pub location: Vec2,
-    pub destroyed: bool,
 }
 
-pub struct ItemRenderer<
-    ExtraData: Clone,
-    Child: for<'a> Component<&'a ChildProperties<ExtraData>, &'a mut ChildProperties<ExtraData>>,
-> {
-    _extra_data: PhantomData<ExtraData>,
-    child: Child,
-}
-
-impl<
-        ExtraData: Clone,
-        Child: for<'a> Component<&'a ChildProperties<ExtraData>, &'a mut ChildProperties<ExtraData>>,
-    > Component<&ItemRendererProperties<ExtraData>, &mut ItemRendererProperties<ExtraData>>
-    for ItemRenderer<ExtraData, Child>
-{
-    type Input = Child;
-
-    fn instantiate(input: Self::Input) -> Self
-    where
-        Self: Sized,
-    {
-        Self {
-            _extra_data: PhantomData,
-            child: input,
-        }
-    }
-
-    fn process<'c>(
-        &mut self,
-        context: &Context,
-        state: &'c mut ItemRendererProperties<ExtraData>,
-    ) -> &'c mut ItemRendererProperties<ExtraData> {
-        state.items = state
-            .items
-            .drain(0..)
-            .filter_map(|mut v| {
-                let mut child_state = ChildProperties {
-                    destroyed: false,
-                    extra_data: state.extra_data.to_owned(),
-                    location: v,
-                };
-                self.child.process(context, &mut child_state);
-                state.extra_data = child_state.extra_data;
-                v = child_state.location;
-                if child_state.destroyed {
-                    None
-                } else {
-                    Some(v)
-                }
-            })
-            .collect();
-        state
+impl<ExtraData> SpawnerConfig<ExtraData, SpawnedItem> {
+    pub fn spawn(&mut self, location: Vec2) {
+        let id = self
+            .spawned_items
+            .iter()
+            .map(|v| v.id + 1)
+            .max()
+            .unwrap_or_default();
+        self.spawned_items.push(SpawnedItem { id, location })
     }
+}
 
-    fn render(&self, context: &Context, props: &ItemRendererProperties<ExtraData>) {
-        for v in &props.items {
-            let child_state = ChildProperties {
-                destroyed: false,
-                extra_data: props.extra_data.to_owned(),
-                location: *v,
-            };
-            self.child.render(context, &child_state);
-        }
-    }
-    fn ui<'c>(
-        &mut self,
-        context: &Context,
-        ui: &mut macroquad::ui::Ui,
-        state: &'c mut ItemRendererProperties<ExtraData>,
-    ) -> &'c mut ItemRendererProperties<ExtraData> {
-        for v in &mut state.items {
-            let mut child_state = ChildProperties {
-                destroyed: false,
-                extra_data: state.extra_data.to_owned(),
-                location: *v,
-            };
-            self.child.ui(context, ui, &mut child_state);
-        }
-        state
-    }
+pub fn spawned_item_key(item: &SpawnedItem) -> u64 {
+    item.id
 }
 
 #[derive(Debug, Clone)]
@@ -182,15 +118,18 @@ pub struct CoinProperties {
     pub coin_speed: f32,
     pub player_loc: Vec2,
     pub player_size: Vec2,
-    pub touched_coins: i64,
-    pub coins_let_through: i64,
+    pub events: EventQueue<GameEvent>,
+    pub spawn_timer: TimerState,
 }
 
 pub struct SingleCoinRenderer {
     renderer: Rectangle,
 }
-impl Component<&ChildProperties<CoinProperties>, &mut ChildProperties<CoinProperties>>
-    for SingleCoinRenderer
+impl
+    Component<
+        &ItemProperties<SpawnedItem, CoinProperties>,
+        &mut ItemProperties<SpawnedItem, CoinProperties>,
+    > for SingleCoinRenderer
 {
     type Input = ();
 
@@ -206,42 +145,42 @@ impl Component<&ChildProperties<CoinProperties>, &mut ChildProperties<CoinProper
     fn process<'c>(
         &mut self,
         context: &Context,
-        state: &'c mut ChildProperties<CoinProperties>,
-    ) -> &'c mut ChildProperties<CoinProperties> {
-        let mut coin_loc = state.location;
+        state: &'c mut ItemProperties<SpawnedItem, CoinProperties>,
+    ) -> &'c mut ItemProperties<SpawnedItem, CoinProperties> {
+        let mut coin_loc = state.item.location;
         let frame_time = context.get_delta();
         let window_height = context.viewport_size().y;
         let player_rec = Rect::new(
-            state.extra_data.player_loc.x,
-            state.extra_data.player_loc.y,
-            state.extra_data.player_size.x,
-            state.extra_data.player_size.y,
+            state.shared.player_loc.x,
+            state.shared.player_loc.y,
+            state.shared.player_size.x,
+            state.shared.player_size.y,
         );
-        coin_loc.y += frame_time * state.extra_data.coin_speed;
-        state.location.y = coin_loc.y;
+        coin_loc.y += frame_time * state.shared.coin_speed;
+        state.item.location.y = coin_loc.y;
         let coin_rec = Rect::new(
             coin_loc.x,
             coin_loc.y,
-            state.extra_data.coin_size,
-            state.extra_data.coin_size,
+            state.shared.coin_size,
+            state.shared.coin_size,
         );
         if player_rec.overlaps(&coin_rec) {
-            state.extra_data.touched_coins += 1;
-            state.destroyed = true;
+            state.shared.events.send(context, GameEvent::CoinTouched);
+            state.removed = true;
         } else if coin_loc.y > window_height {
-            state.extra_data.coins_let_through += 1;
-            state.destroyed = true;
+            state.shared.events.send(context, GameEvent::CoinLetThrough);
+            state.removed = true;
         }
         state
     }
 
-    fn render(&self, context: &Context, props: &ChildProperties<CoinProperties>) {
+    fn render(&self, context: &Context, props: &ItemProperties<SpawnedItem, CoinProperties>) {
         self.renderer.render(
             context,
             &RectangleProps {
-                size: vec2(props.extra_data.coin_size, props.extra_data.coin_size),
+                size: vec2(props.shared.coin_size, props.shared.coin_size),
                 color: YELLOW,
-                location: props.location,
+                location: props.item.location,
             },
         )
     }
@@ -249,30 +188,44 @@ impl Component<&ChildProperties<CoinProperties>, &mut ChildProperties<CoinProper
         &mut self,
         context: &Context,
         ui: &mut macroquad::ui::Ui,
-        state: &'c mut ChildProperties<CoinProperties>,
-    ) -> &'c mut ChildProperties<CoinProperties> {
+        state: &'c mut ItemProperties<SpawnedItem, CoinProperties>,
+    ) -> &'c mut ItemProperties<SpawnedItem, CoinProperties> {
         self.renderer.ui(
             context,
             ui,
             &mut RectangleProps {
-                size: vec2(state.extra_data.coin_size, state.extra_data.coin_size),
+                size: vec2(state.shared.coin_size, state.shared.coin_size),
                 color: YELLOW,
-                location: state.location,
+                location: state.item.location,
             },
         );
         state
     }
 }
 
-type CoinSpawner =
-    Spawner<Vec2, CoinProperties, fn(&Context, &mut SpawnerConfig<CoinProperties, Vec2>)>;
+type CoinSpawner = Spawner<
+    SpawnedItem,
+    CoinProperties,
+    fn(&Context, &mut SpawnerConfig<CoinProperties, SpawnedItem>),
+>;
+
+type CoinRenderer = ForEach<
+    fn(&SpawnedItem) -> u64,
+    fn(&SpawnedItem) -> SingleCoinRenderer,
+    u64,
+    SpawnedItem,
+    SingleCoinRenderer,
+>;
 
 pub struct Coins {
     spawner: CoinSpawner,
-    renderer: ItemRenderer<CoinProperties, SingleCoinRenderer>,
+    renderer: CoinRenderer,
 }
-impl Component<&ItemRendererProperties<CoinProperties>, &mut ItemRendererProperties<CoinProperties>>
-    for Coins
+impl
+    Component<
+        &ForEachProperties<SpawnedItem, CoinProperties>,
+        &mut ForEachProperties<SpawnedItem, CoinProperties>,
+    > for Coins
 {
     type Input = ();
 
@@ -280,48 +233,48 @@ impl Component<&ItemRendererProperties<CoinProperties>, &mut ItemRendererPropert
     where
         Self: Sized,
     {
-        fn on_reached_time(context: &Context, props: &mut SpawnerConfig<CoinProperties, Vec2>) {
+        fn on_reached_time(
+            context: &Context,
+            props: &mut SpawnerConfig<CoinProperties, SpawnedItem>,
+        ) {
             if f32::gen_range(0., 1.) < props.extra_data.spawn_chance {
-                props.spawned_items.push(vec2(
+                props.spawn(vec2(
                     f32::gen_range(0., context.viewport_size().x - props.extra_data.coin_size),
                     0., //f32::gen_range(0., screen_height() - state.coin_size),
                 ))
             }
         }
+        fn create_child(_: &SpawnedItem) -> SingleCoinRenderer {
+            SingleCoinRenderer::instantiate(())
+        }
         Self {
             spawner: Spawner::new(on_reached_time),
-            renderer: ItemRenderer {
-                _extra_data: PhantomData,
-                child: SingleCoinRenderer {
-                    renderer: Rectangle,
-                },
-            },
+            renderer: ForEach::new(spawned_item_key, create_child),
         }
     }
 
     fn process<'c>(
         &mut self,
         context: &Context,
-        state: &'c mut ItemRendererProperties<CoinProperties>,
-    ) -> &'c mut ItemRendererProperties<CoinProperties> {
+        state: &'c mut ForEachProperties<SpawnedItem, CoinProperties>,
+    ) -> &'c mut ForEachProperties<SpawnedItem, CoinProperties> {
         let mut spawner_config = SpawnerConfig {
-            max_time: 1.0 / 60.,
-            size: vec2(state.extra_data.coin_size, state.extra_data.coin_size),
-            extra_data: state.extra_data.to_owned(),
+            timer: state.shared.spawn_timer.to_owned(),
+            extra_data: state.shared.to_owned(),
             spawned_items: state.items.to_owned(),
         };
         self.spawner.process(context, &mut spawner_config);
-        state.extra_data = spawner_config.extra_data;
+        state.shared = spawner_config.extra_data;
+        state.shared.spawn_timer = spawner_config.timer;
         state.items = spawner_config.spawned_items;
         self.renderer.process(context, state);
         state
     }
 
-    fn render(&self, context: &Context, props: &ItemRendererProperties<CoinProperties>) {
+    fn render(&self, context: &Context, props: &ForEachProperties<SpawnedItem, CoinProperties>) {
         let spawner_config = SpawnerConfig {
-            max_time: 1.0 / 60.,
-            size: vec2(props.extra_data.coin_size, props.extra_data.coin_size),
-            extra_data: props.extra_data.to_owned(),
+            timer: props.shared.spawn_timer.to_owned(),
+            extra_data: props.shared.to_owned(),
             spawned_items: props.items.to_owned(),
         };
         self.spawner.render(context, &spawner_config);
@@ -332,12 +285,11 @@ impl Component<&ItemRendererProperties<CoinProperties>, &mut ItemRendererPropert
         &mut self,
         context: &Context,
         ui: &mut macroquad::ui::Ui,
-        state: &'c mut ItemRendererProperties<CoinProperties>,
-    ) -> &'c mut ItemRendererProperties<CoinProperties> {
+        state: &'c mut ForEachProperties<SpawnedItem, CoinProperties>,
+    ) -> &'c mut ForEachProperties<SpawnedItem, CoinProperties> {
         let mut spawner_config = SpawnerConfig {
-            max_time: 1.0 / 60.,
-            size: vec2(state.extra_data.coin_size, state.extra_data.coin_size),
-            extra_data: state.extra_data.to_owned(),
+            timer: state.shared.spawn_timer.to_owned(),
+            extra_data: state.shared.to_owned(),
             spawned_items: state.items.to_owned(),
         };
         self.spawner.ui(context, ui, &mut spawner_config);
@@ -1,26 +1,18 @@
-use macroquad::{
-    prelude::{KeyCode, Vec2},
-    time::get_frame_time,
-};
-use monad_quad::components::{events::KeyDown, Component, Context};
+use macroquad::prelude::Vec2;
+use monad_quad::components::{events::Axis2dListener, input::InputMap, Component, Context};
+
+use crate::settings::{MOVE_X, MOVE_Y};
 
 #[derive(Clone)]
 pub struct ControlProps {
-    pub left: KeyCode,
-    pub right: KeyCode,
-    pub up: KeyCode,
-    pub down: KeyCode,
-    pub speed: f32,
+    pub bindings: InputMap,
     pub dir: Vec2,
 }
 
-type BasicKeyDown = KeyDown<fn(&ControlProps) -> KeyCode, fn(bool, &mut ControlProps)>;
+type Movement = Axis2dListener<fn(&ControlProps) -> &InputMap, fn(Vec2, &mut ControlProps)>;
 
 pub struct Controls {
-    left: BasicKeyDown,
-    right: BasicKeyDown,
-    up: BasicKeyDown,
-    down: BasicKeyDown,
+    movement: Movement,
 }
 impl Component<&ControlProps, &mut ControlProps> for Controls {
     type Input = ();
@@ -29,44 +21,15 @@ impl Component<&ControlProps, &mut ControlProps> for Controls {
     where
         Self: Sized,
     {
-        fn left(state: &ControlProps) -> KeyCode {
-            state.left
-        }
-        fn right(state: &ControlProps) -> KeyCode {
-            state.right
-        }
-        fn up(state: &ControlProps) -> KeyCode {
-            state.up
-        }
-        fn down(state: &ControlProps) -> KeyCode {
-            state.down
-        }
-        fn left_map(b: bool, state: &mut ControlProps) {
-            if b {
-                state.dir.x -= get_frame_time() * state.speed
-            }
-        }
-        fn right_map(b: bool, state: &mut ControlProps) {
-            if b {
-                state.dir.x += get_frame_time() * state.speed
-            }
-        }
-        fn up_map(b: bool, state: &mut ControlProps) {
-            if b {
-                state.dir.y -= get_frame_time() * state.speed
-            }
+        fn bindings(state: &ControlProps) -> &InputMap {
+            &state.bindings
         }
-        fn down_map(b: bool, state: &mut ControlProps) {
-            if b {
-                state.dir.y += get_frame_time() * state.speed
-            }
+        fn movement(dir: Vec2, state: &mut ControlProps) {
+            state.dir = dir
         }
 
         Self {
-            left: KeyDown::new::<ControlProps>(left, left_map),
-            right: KeyDown::new::<ControlProps>(right, right_map),
-            down: KeyDown::new::<ControlProps>(down, down_map),
-            up: KeyDown::new::<ControlProps>(up, up_map),
+            movement: Axis2dListener::new::<ControlProps>(MOVE_X, MOVE_Y, bindings, movement),
         }
     }
 
@@ -75,11 +38,7 @@ impl Component<&ControlProps, &mut ControlProps> for Controls {
         context: &Context,
         state: &'c mut ControlProps,
     ) -> &'c mut ControlProps {
-        Component::<&ControlProps, &mut ControlProps>::process(&mut self.left, context, state);
-        Component::<&ControlProps, &mut ControlProps>::process(&mut self.right, context, state);
-        Component::<&ControlProps, &mut ControlProps>::process(&mut self.up, context, state);
-        Component::<&ControlProps, &mut ControlProps>::process(&mut self.down, context, state);
-        state.dir = state.dir.normalize_or_zero();
+        self.movement.process(context, state);
         state
     }
 }
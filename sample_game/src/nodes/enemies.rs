@@ -3,13 +3,15 @@ use macroquad::{
     rand::RandomRange,
 };
 use monad_quad::components::{
+    events::{EventQueue, TimerState},
+    logic::{ForEach, ForEachProperties, ItemProperties},
     render::{Rectangle, RectangleProps},
     Component, Context,
 };
 
 use super::{
-    coins::{Spawner, SpawnerConfig},
-    ChildProperties, ItemRenderer, ItemRendererProperties,
+    coins::{spawned_item_key, SpawnedItem, Spawner, SpawnerConfig},
+    GameEvent,
 };
 
 #[derive(Debug, Clone)]
@@ -20,16 +22,19 @@ pub struct EnemyProperties {
     pub enemies_chance: f32,
     pub player_loc: Vec2,
     pub player_size: Vec2,
-    pub got_hit: bool,
-    pub reached_the_end: i64,
+    pub events: EventQueue<GameEvent>,
+    pub spawn_timer: TimerState,
 }
 
 pub struct SingleEnemyRenderer {
     child: Rectangle,
 }
 
-impl Component<&ChildProperties<EnemyProperties>, &mut ChildProperties<EnemyProperties>>
-    for SingleEnemyRenderer
+impl
+    Component<
+        &ItemProperties<SpawnedItem, EnemyProperties>,
+        &mut ItemProperties<SpawnedItem, EnemyProperties>,
+    > for SingleEnemyRenderer
 {
     type Input = ();
 
@@ -43,39 +48,42 @@ impl Component<&ChildProperties<EnemyProperties>, &mut ChildProperties<EnemyProp
     fn process<'c>(
         &mut self,
         context: &Context,
-        state: &'c mut ChildProperties<EnemyProperties>,
-    ) -> &'c mut ChildProperties<EnemyProperties> {
+        state: &'c mut ItemProperties<SpawnedItem, EnemyProperties>,
+    ) -> &'c mut ItemProperties<SpawnedItem, EnemyProperties> {
         let frame_time = context.get_delta();
         let player_rec = Rect::new(
-            state.extra_data.player_loc.x,
-            state.extra_data.player_loc.y,
-            state.extra_data.player_size.x,
-            state.extra_data.player_size.y,
+            state.shared.player_loc.x,
+            state.shared.player_loc.y,
+            state.shared.player_size.x,
+            state.shared.player_size.y,
         );
-        state.location.y -= frame_time * state.extra_data.enemies_speed;
+        state.item.location.y -= frame_time * state.shared.enemies_speed;
         let enemy_rec = Rect::new(
-            state.location.x,
-            state.location.y,
-            state.extra_data.enemies_size,
-            state.extra_data.enemies_size,
+            state.item.location.x,
+            state.item.location.y,
+            state.shared.enemies_size,
+            state.shared.enemies_size,
         );
         if player_rec.overlaps(&enemy_rec) {
-            state.extra_data.got_hit = true;
-            state.destroyed = true;
-        } else if state.location.y < 0. {
-            state.extra_data.reached_the_end += 1;
-            state.destroyed = true;
+            state.shared.events.send(context, GameEvent::EnemyHitPlayer);
+            state.removed = true;
+        } else if state.item.location.y < 0. {
+            state
+                .shared
+                .events
+                .send(context, GameEvent::EnemyReachedTheEnd);
+            state.removed = true;
         }
         state
     }
 
-    fn render(&self, context: &Context, props: &ChildProperties<EnemyProperties>) {
+    fn render(&self, context: &Context, props: &ItemProperties<SpawnedItem, EnemyProperties>) {
         self.child.render(
             context,
             &RectangleProps {
-                size: vec2(props.extra_data.enemies_size, props.extra_data.enemies_size),
-                color: props.extra_data.enemies_color,
-                location: props.location,
+                size: vec2(props.shared.enemies_size, props.shared.enemies_size),
+                color: props.shared.enemies_color,
+                location: props.item.location,
             },
         )
     }
@@ -83,32 +91,43 @@ impl Component<&ChildProperties<EnemyProperties>, &mut ChildProperties<EnemyProp
         &mut self,
         context: &Context,
         ui: &mut macroquad::ui::Ui,
-        state: &'c mut ChildProperties<EnemyProperties>,
-    ) -> &'c mut ChildProperties<EnemyProperties> {
+        state: &'c mut ItemProperties<SpawnedItem, EnemyProperties>,
+    ) -> &'c mut ItemProperties<SpawnedItem, EnemyProperties> {
         self.child.ui(
             context,
             ui,
             &mut RectangleProps {
-                size: vec2(state.extra_data.enemies_size, state.extra_data.enemies_size),
-                color: state.extra_data.enemies_color,
-                location: state.location,
+                size: vec2(state.shared.enemies_size, state.shared.enemies_size),
+                color: state.shared.enemies_color,
+                location: state.item.location,
             },
         );
         state
     }
 }
 
-type EnemySpawner =
-    Spawner<Vec2, EnemyProperties, fn(&Context, &mut SpawnerConfig<EnemyProperties, Vec2>)>;
+type EnemySpawner = Spawner<
+    SpawnedItem,
+    EnemyProperties,
+    fn(&Context, &mut SpawnerConfig<EnemyProperties, SpawnedItem>),
+>;
+
+type EnemyRenderer = ForEach<
+    fn(&SpawnedItem) -> u64,
+    fn(&SpawnedItem) -> SingleEnemyRenderer,
+    u64,
+    SpawnedItem,
+    SingleEnemyRenderer,
+>;
 
 pub struct Enemies {
     spawner: EnemySpawner,
-    renderer: ItemRenderer<EnemyProperties, SingleEnemyRenderer>,
+    renderer: EnemyRenderer,
 }
 impl
     Component<
-        &ItemRendererProperties<EnemyProperties>,
-        &mut ItemRendererProperties<EnemyProperties>,
+        &ForEachProperties<SpawnedItem, EnemyProperties>,
+        &mut ForEachProperties<SpawnedItem, EnemyProperties>,
     > for Enemies
 {
     type Input = ();
@@ -117,10 +136,13 @@ impl
     where
         Self: Sized,
     {
-        fn on_reached_time(context: &Context, props: &mut SpawnerConfig<EnemyProperties, Vec2>) {
+        fn on_reached_time(
+            context: &Context,
+            props: &mut SpawnerConfig<EnemyProperties, SpawnedItem>,
+        ) {
             let window_height = context.viewport_size().y;
             if f32::gen_range(0., 1.) < props.extra_data.enemies_chance {
-                props.spawned_items.push(vec2(
+                props.spawn(vec2(
                     f32::gen_range(
                         0.,
                         context.viewport_size().x + props.extra_data.enemies_size,
@@ -129,35 +151,37 @@ impl
                 ))
             }
         }
+        fn create_child(_: &SpawnedItem) -> SingleEnemyRenderer {
+            SingleEnemyRenderer::instantiate(())
+        }
         Self {
             spawner: Spawner::new(on_reached_time),
-            renderer: ItemRenderer::instantiate(SingleEnemyRenderer::instantiate(())),
+            renderer: ForEach::new(spawned_item_key, create_child),
         }
     }
 
     fn process<'c>(
         &mut self,
         context: &Context,
-        state: &'c mut ItemRendererProperties<EnemyProperties>,
-    ) -> &'c mut ItemRendererProperties<EnemyProperties> {
+        state: &'c mut ForEachProperties<SpawnedItem, EnemyProperties>,
+    ) -> &'c mut ForEachProperties<SpawnedItem, EnemyProperties> {
         let mut config = SpawnerConfig {
-            max_time: 1.0 / 60.,
-            size: vec2(state.extra_data.enemies_size, state.extra_data.enemies_size),
-            extra_data: state.extra_data.to_owned(),
+            timer: state.shared.spawn_timer.to_owned(),
+            extra_data: state.shared.to_owned(),
             spawned_items: state.items.to_owned(),
         };
         self.spawner.process(context, &mut config);
-        state.extra_data = config.extra_data;
+        state.shared = config.extra_data;
+        state.shared.spawn_timer = config.timer;
         state.items = config.spawned_items;
         self.renderer.process(context, state);
         state
     }
 
-    fn render(&self, context: &Context, props: &ItemRendererProperties<EnemyProperties>) {
+    fn render(&self, context: &Context, props: &ForEachProperties<SpawnedItem, EnemyProperties>) {
         let config = SpawnerConfig {
-            max_time: 1.0 / 60.,
-            size: vec2(props.extra_data.enemies_size, props.extra_data.enemies_size),
-            extra_data: props.extra_data.to_owned(),
+            timer: props.shared.spawn_timer.to_owned(),
+            extra_data: props.shared.to_owned(),
             spawned_items: props.items.to_owned(),
         };
         self.spawner.render(context, &config);
@@ -167,12 +191,11 @@ impl
         &mut self,
         context: &Context,
         ui: &mut macroquad::ui::Ui,
-        state: &'c mut ItemRendererProperties<EnemyProperties>,
-    ) -> &'c mut ItemRendererProperties<EnemyProperties> {
+        state: &'c mut ForEachProperties<SpawnedItem, EnemyProperties>,
+    ) -> &'c mut ForEachProperties<SpawnedItem, EnemyProperties> {
         let mut config = SpawnerConfig {
-            max_time: 1.0 / 60.,
-            size: vec2(state.extra_data.enemies_size, state.extra_data.enemies_size),
-            extra_data: state.extra_data.to_owned(),
+            timer: state.shared.spawn_timer.to_owned(),
+            extra_data: state.shared.to_owned(),
             spawned_items: state.items.to_owned(),
         };
         self.spawner.ui(context, ui, &mut config);
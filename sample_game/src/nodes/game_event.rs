@@ -0,0 +1,8 @@
+/// Everything that happened during a frame that influences the score or the lives of the player
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameEvent {
+    CoinTouched,
+    CoinLetThrough,
+    EnemyHitPlayer,
+    EnemyReachedTheEnd,
+}
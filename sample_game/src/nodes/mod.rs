@@ -1,14 +1,13 @@
 mod coins;
 mod control;
 mod enemies;
+mod game_event;
 mod player;
 mod score_display;
 
-pub use coins::{
-    ChildProperties, CoinProperties, Coins, ItemRenderer, ItemRendererProperties,
-    SingleCoinRenderer,
-};
+pub use coins::{CoinProperties, Coins, SpawnedItem};
 pub use control::{ControlProps, Controls};
 pub use enemies::{Enemies, EnemyProperties};
+pub use game_event::GameEvent;
 pub use player::{Player, PlayerProps};
 pub use score_display::{ScoreDisplay, ScoreDisplayProperties};
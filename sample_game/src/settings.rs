@@ -1,9 +1,10 @@
 use std::path::PathBuf;
 
 use macroquad::{
-    prelude::{error, vec2, warn, Vec2},
+    prelude::{error, vec2, warn, KeyCode, Vec2},
     window::request_new_screen_size,
 };
+use monad_quad::components::input::{AxisBinding, Binding, InputMap};
 
 fn config_dir() -> Option<PathBuf> {
     let x = directories::ProjectDirs::from("", "monad_quad", "sample_game")
@@ -33,10 +34,44 @@ pub const RESOLUTIONS: [(Vec2, &str); 5] = [
     (vec2(7680., 4320.), "7680 x 4320 (8K FUHD/4320p)"),
 ];
 
-#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize, Default)]
+pub const MOVE_X: &str = "move_x";
+pub const MOVE_Y: &str = "move_y";
+pub const PAUSE: &str = "pause";
+
+fn default_controls() -> InputMap {
+    InputMap::new()
+        .with_axis(
+            MOVE_X,
+            AxisBinding {
+                negative: vec![Binding::key(KeyCode::Left), Binding::key(KeyCode::A)],
+                positive: vec![Binding::key(KeyCode::Right), Binding::key(KeyCode::D)],
+            },
+        )
+        .with_axis(
+            MOVE_Y,
+            AxisBinding {
+                negative: vec![Binding::key(KeyCode::Up), Binding::key(KeyCode::W)],
+                positive: vec![Binding::key(KeyCode::Down), Binding::key(KeyCode::S)],
+            },
+        )
+        .with_action(PAUSE, KeyCode::P)
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Settings {
     pub is_fullscreen: bool,
     pub selected_size: usize,
+    #[serde(default = "default_controls")]
+    pub controls: InputMap,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            is_fullscreen: false,
+            selected_size: 0,
+            controls: default_controls(),
+        }
+    }
 }
 impl Settings {
     pub async fn read_from_settings_or_default() -> Self {
@@ -84,6 +119,10 @@ impl Settings {
             self.set_screen_size();
             save_file = true
         }
+        if self.controls != new.controls {
+            self.controls = new.controls;
+            save_file = true
+        }
 
         if !save_file {
             return;
@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{components::Context, Component};
+
+use super::{Lerp, TrackValues, TweenKind};
+
+fn linear() -> TweenKind {
+    TweenKind::Linear
+}
+
+/// The value a track has at a point in time
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub time: f32,
+    pub value: f32,
+    /// How the track moves from the previous keyframe to this one.
+    ///
+    /// A [TweenKind::Custom] can't be serialized, saving a clip that holds one fails
+    #[serde(default = "linear")]
+    pub tween_kind: TweenKind,
+}
+
+impl Keyframe {
+    pub fn new(time: f32, value: f32, tween_kind: TweenKind) -> Self {
+        Self {
+            time,
+            value,
+            tween_kind,
+        }
+    }
+}
+
+/// A list of keyframes, sorted by their time
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<Keyframe>", into = "Vec<Keyframe>")]
+pub struct Track {
+    keyframes: Vec<Keyframe>,
+}
+
+impl Track {
+    pub fn new(keyframes: impl IntoIterator<Item = Keyframe>) -> Self {
+        let mut keyframes: Vec<_> = keyframes.into_iter().collect();
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self { keyframes }
+    }
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+    /// the time of the last keyframe
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map(|v| v.time).unwrap_or_default()
+    }
+    /// The value of the track at the given time. Before the first keyframe and after the last the track keeps their value
+    pub fn sample(&self, time: f32) -> Option<f32> {
+        let next = self.keyframes.iter().position(|v| v.time > time);
+        match next {
+            None => self.keyframes.last().map(|v| v.value),
+            Some(0) => self.keyframes.first().map(|v| v.value),
+            Some(index) => {
+                let previous = &self.keyframes[index - 1];
+                let next = &self.keyframes[index];
+                let progress = (time - previous.time) / (next.time - previous.time);
+                Some(
+                    previous
+                        .value
+                        .lerp(&next.value, next.tween_kind.ease(progress)),
+                )
+            }
+        }
+    }
+}
+
+impl From<Vec<Keyframe>> for Track {
+    fn from(value: Vec<Keyframe>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Track> for Vec<Keyframe> {
+    fn from(value: Track) -> Self {
+        value.keyframes
+    }
+}
+
+/// A set of named tracks that play together, like a bob or a blink.
+///
+/// Clips can be stored as JSON with the `serde_json` feature enabled, or as RON with the `ron` feature enabled
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    /// how long the clip lasts, defaults to the time of the last keyframe of any track
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+    pub tracks: BTreeMap<String, Track>,
+}
+
+impl AnimationClip {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_track(
+        mut self,
+        name: impl Into<String>,
+        keyframes: impl IntoIterator<Item = Keyframe>,
+    ) -> Self {
+        self.tracks.insert(name.into(), Track::new(keyframes));
+        self
+    }
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+    pub fn duration(&self) -> f32 {
+        self.duration
+            .unwrap_or_else(|| self.tracks.values().map(Track::duration).fold(0., f32::max))
+    }
+    /// the value of every track at the given time
+    pub fn sample(&self, time: f32) -> TrackValues {
+        let mut values = TrackValues::default();
+        for (name, track) in &self.tracks {
+            if let Some(value) = track.sample(time) {
+                values.insert(name.clone(), value)
+            }
+        }
+        values
+    }
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+    #[cfg(feature = "ron")]
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+    #[cfg(feature = "ron")]
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+/// How far an [AnimationClip] got, meant to live in the state
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnimationPlayback {
+    pub time: f32,
+    pub playing: bool,
+    /// starts over once the end of the clip is reached, instead of stopping there
+    pub looping: bool,
+    /// how fast the clip plays, 1 being normal speed
+    pub speed: f32,
+}
+
+impl Default for AnimationPlayback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationPlayback {
+    /// creates a playback that starts playing right away, only once and at normal speed
+    pub fn new() -> Self {
+        Self {
+            time: 0.,
+            playing: true,
+            looping: false,
+            speed: 1.,
+        }
+    }
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    pub fn restart(&mut self) {
+        self.time = 0.;
+        self.playing = true;
+    }
+    /// moves the playback forward by `delta` seconds, for a clip of the given duration.
+    ///
+    /// A clip that doesn't loop stops playing once it reaches its end
+    pub fn advance(&mut self, delta: f32, duration: f32) {
+        if !self.playing {
+            return;
+        }
+        self.time += delta * self.speed;
+        if duration <= 0. {
+            self.time = 0.;
+        } else if self.looping {
+            self.time = self.time.rem_euclid(duration);
+        } else if self.time >= duration {
+            self.time = duration;
+            self.playing = false;
+        } else if self.time < 0. {
+            self.time = 0.;
+            self.playing = false;
+        }
+    }
+}
+
+/// Plays an [AnimationClip] using the [AnimationPlayback] that `get_playback` points to.
+///
+/// Every process `on_update` gets the values of the tracks, to write them into whatever fields they are bound to
+pub struct Animation<GetPlayback, OnUpdate> {
+    clip: AnimationClip,
+    get_playback: GetPlayback,
+    on_update: OnUpdate,
+}
+
+impl<GetPlayback, OnUpdate> Animation<GetPlayback, OnUpdate> {
+    pub fn new<State>(clip: AnimationClip, get_playback: GetPlayback, on_update: OnUpdate) -> Self
+    where
+        GetPlayback: Fn(&mut State) -> &mut AnimationPlayback,
+        OnUpdate: Fn(&TrackValues, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((clip, get_playback, on_update))
+    }
+    pub fn clip(&self) -> &AnimationClip {
+        &self.clip
+    }
+}
+
+impl<
+        State,
+        GetPlayback: Fn(&mut State) -> &mut AnimationPlayback,
+        OnUpdate: Fn(&TrackValues, &mut State),
+    > Component<&State, &mut State> for Animation<GetPlayback, OnUpdate>
+{
+    type Input = (AnimationClip, GetPlayback, OnUpdate);
+
+    fn instantiate((clip, get_playback, on_update): Self::Input) -> Self {
+        Self {
+            clip,
+            get_playback,
+            on_update,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let playback = (self.get_playback)(state);
+        playback.advance(context.get_delta(), self.clip.duration());
+        let values = self.clip.sample(playback.time);
+        (self.on_update)(&values, state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, AnimationClip, AnimationPlayback, Keyframe, Track};
+    use crate::{
+        components::animation::{TrackValues, TweenKind},
+        testing::TestHarness,
+    };
+
+    fn bob() -> AnimationClip {
+        AnimationClip::new()
+            .with_track(
+                "y",
+                [
+                    Keyframe::new(1., 10., TweenKind::Linear),
+                    Keyframe::new(0., 0., TweenKind::Linear),
+                    Keyframe::new(2., 0., TweenKind::EaseInQuad),
+                ],
+            )
+            .with_track("x", [Keyframe::new(0.5, 4., TweenKind::Linear)])
+    }
+
+    #[test]
+    fn track_holds_its_first_and_last_value() {
+        let track = bob().tracks["y"].clone();
+        assert_eq!(track.duration(), 2.);
+        assert_eq!(track.sample(-1.), Some(0.));
+        assert_eq!(track.sample(5.), Some(0.));
+        assert_eq!(Track::default().sample(0.), None);
+    }
+
+    #[test]
+    fn track_moves_between_keyframes_with_the_curve_of_the_next_one() {
+        let track = bob().tracks["y"].clone();
+        assert_eq!(track.sample(0.5), Some(5.));
+        assert_eq!(track.sample(1.), Some(10.));
+        // ease in quad at 0.5 is 0.25 of the way from 10 to 0
+        assert_eq!(track.sample(1.5), Some(7.5));
+    }
+
+    #[test]
+    fn clip_samples_every_track() {
+        let clip = bob();
+        assert_eq!(clip.duration(), 2.);
+        assert_eq!(clip.with_duration(3.).duration(), 3.);
+        let values = bob().sample(0.25);
+        assert_eq!(values.get("x"), Some(4.));
+        assert_eq!(values.get("y"), Some(2.5));
+    }
+
+    #[test]
+    fn playback_stops_at_the_end() {
+        let mut playback = AnimationPlayback::new();
+        playback.advance(1.5, 2.);
+        assert_eq!(playback.time, 1.5);
+        playback.advance(1., 2.);
+        assert_eq!(playback.time, 2.);
+        assert!(!playback.playing);
+        playback.advance(1., 2.);
+        assert_eq!(playback.time, 2.);
+        playback.restart();
+        assert_eq!(playback.time, 0.);
+        assert!(playback.playing);
+    }
+
+    #[test]
+    fn looping_playback_wraps_around() {
+        let mut playback = AnimationPlayback::new().looping();
+        playback.advance(2.5, 2.);
+        assert_eq!(playback.time, 0.5);
+        assert!(playback.playing);
+
+        let mut playback = AnimationPlayback::new().looping().with_speed(-1.);
+        playback.advance(0.5, 2.);
+        assert_eq!(playback.time, 1.5);
+    }
+
+    #[test]
+    fn negative_speed_plays_backwards_and_stops_at_the_start() {
+        let mut playback = AnimationPlayback::new().with_speed(-2.);
+        playback.time = 1.;
+        playback.advance(0.25, 2.);
+        assert_eq!(playback.time, 0.5);
+        playback.advance(0.5, 2.);
+        assert_eq!(playback.time, 0.);
+        assert!(!playback.playing);
+    }
+
+    #[derive(Default)]
+    struct State {
+        playback: AnimationPlayback,
+        y: f32,
+    }
+
+    fn playback(state: &mut State) -> &mut AnimationPlayback {
+        &mut state.playback
+    }
+
+    fn write_y(values: &TrackValues, state: &mut State) {
+        state.y = values.get_or("y", state.y)
+    }
+
+    #[test]
+    fn animation_writes_the_track_values() {
+        let mut harness =
+            TestHarness::new(State::default(), Animation::new(bob(), playback, write_y));
+        harness.frame(0.5);
+        assert_eq!(harness.state().y, 5.);
+        harness.frames(4, 0.5);
+        assert_eq!(harness.state().y, 0.);
+        assert!(!harness.state().playback.playing);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn clip_json_round_trip() {
+        let clip = bob().with_duration(3.);
+        let json = clip.to_json().unwrap();
+        assert_eq!(AnimationClip::from_json(&json).unwrap(), clip);
+        let written = AnimationClip::from_json(
+            r#"{ "tracks": { "y": [ { "time": 1, "value": 2 }, { "time": 0, "value": 0 } ] } }"#,
+        )
+        .unwrap();
+        assert_eq!(written.duration(), 1.);
+        assert_eq!(
+            written.tracks["y"].keyframes()[1].tween_kind,
+            TweenKind::Linear
+        );
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn clip_ron_round_trip() {
+        let clip = bob().with_track(
+            "z",
+            [Keyframe::new(
+                1.,
+                1.,
+                TweenKind::CubicBezier(0.25, 0.1, 0.25, 1.),
+            )],
+        );
+        let ron = clip.to_ron().unwrap();
+        assert_eq!(AnimationClip::from_ron(&ron).unwrap(), clip);
+    }
+}
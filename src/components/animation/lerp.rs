@@ -0,0 +1,93 @@
+use macroquad::prelude::{Color, Rect, Vec2, Vec3};
+
+/// Values that can be moved smoothly from one to another, so they can be tweened
+pub trait Lerp {
+    /// the value that lies `t` of the way between `self` and `to`.
+    ///
+    /// `t` is 0 at `self` and 1 at `to`, but may go past either end for curves that overshoot
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Vec2::lerp(*self, *to, t)
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Vec3::lerp(*self, *to, t)
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Color::new(
+            self.r.lerp(&to.r, t),
+            self.g.lerp(&to.g, t),
+            self.b.lerp(&to.b, t),
+            self.a.lerp(&to.a, t),
+        )
+    }
+}
+
+impl Lerp for Rect {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Rect::new(
+            self.x.lerp(&to.x, t),
+            self.y.lerp(&to.y, t),
+            self.w.lerp(&to.w, t),
+            self.h.lerp(&to.h, t),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::{vec2, vec3, Color, Rect};
+
+    use super::Lerp;
+
+    #[test]
+    fn lerps_between_the_ends() {
+        assert_eq!(2f32.lerp(&4., 0.), 2.);
+        assert_eq!(2f32.lerp(&4., 0.5), 3.);
+        assert_eq!(2f32.lerp(&4., 1.), 4.);
+        assert_eq!(
+            Lerp::lerp(&vec2(0., 10.), &vec2(10., 0.), 0.25),
+            vec2(2.5, 7.5)
+        );
+        assert_eq!(
+            Lerp::lerp(&vec3(0., 0., 0.), &vec3(2., 4., 8.), 0.5),
+            vec3(1., 2., 4.)
+        );
+    }
+
+    #[test]
+    fn lerps_every_channel_of_a_color() {
+        let from = Color::new(0., 1., 0., 0.);
+        let to = Color::new(1., 0., 0.5, 1.);
+        assert_eq!(from.lerp(&to, 0.5), Color::new(0.5, 0.5, 0.25, 0.5));
+        assert_eq!(from.lerp(&to, 0.), from);
+        assert_eq!(from.lerp(&to, 1.), to);
+    }
+
+    #[test]
+    fn lerps_position_and_size_of_a_rect() {
+        let from = Rect::new(0., 0., 10., 10.);
+        let to = Rect::new(10., 20., 30., 50.);
+        assert_eq!(from.lerp(&to, 0.5), Rect::new(5., 10., 20., 30.));
+    }
+
+    #[test]
+    fn overshoots_past_the_ends() {
+        assert_eq!(0f32.lerp(&10., 1.5), 15.);
+        assert_eq!(0f32.lerp(&10., -0.5), -5.);
+    }
+}
@@ -1,5 +1,15 @@
 mod animate_state;
+mod keyframes;
+mod lerp;
+mod spring;
+mod timeline;
 mod tween;
+mod tween_field;
 
 pub use animate_state::AnimateState;
-pub use tween::{Tween, TweenConfig, TweenKind};
+pub use keyframes::{Animation, AnimationClip, AnimationPlayback, Keyframe, Track};
+pub use lerp::Lerp;
+pub use spring::{smooth_towards, SmoothFollow, Spring, SpringState, SpringValue};
+pub use timeline::{RepeatCount, Timeline, TimelineNode, TimelineState, TrackValues};
+pub use tween::{Tween, TweenConfig, TweenEvent, TweenKind};
+pub use tween_field::{TweenField, Tweened};
@@ -0,0 +1,254 @@
+use std::ops::{Add, Mul, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{components::Context, Component};
+
+/// Values that a [Spring] or [SmoothFollow] can move, like `f32`, `Vec2` and `Vec3`
+pub trait SpringValue:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>> SpringValue for T {}
+
+/// The value a [Spring] moves together with how fast it is moving, meant to live in the state
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpringState<T> {
+    pub value: T,
+    pub velocity: T,
+}
+
+impl<T: Default> SpringState<T> {
+    /// creates a spring that is standing still at the given value
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            velocity: T::default(),
+        }
+    }
+}
+
+impl<T: SpringValue> SpringState<T> {
+    /// Moves the value towards `target` like a critically damped spring, so it gets there as fast as possible without overshooting.
+    ///
+    /// `smooth_time` is roughly how long in seconds it takes to reach the target.
+    /// The result is the same no matter how the time is divided over the frames
+    pub fn update(&mut self, target: T, smooth_time: f32, delta: f32) {
+        if smooth_time <= 0. {
+            self.value = target;
+            self.velocity = self.velocity * 0.;
+            return;
+        }
+        let omega = 2. / smooth_time;
+        let decay = (-omega * delta).exp();
+        let change = self.value - target;
+        let temp = (self.velocity + change * omega) * delta;
+        self.velocity = (self.velocity - temp * omega) * decay;
+        self.value = target + (change + temp) * decay;
+    }
+}
+
+/// Moves the [SpringState] that `get_spring` points to towards the target that `get_target` returns.
+///
+/// Unlike a tween the target can change at any time, the spring smoothly changes course
+pub struct Spring<GetSpring, GetTarget> {
+    smooth_time: f32,
+    get_spring: GetSpring,
+    get_target: GetTarget,
+}
+
+impl<GetSpring, GetTarget> Spring<GetSpring, GetTarget> {
+    pub fn new<State, T>(smooth_time: f32, get_spring: GetSpring, get_target: GetTarget) -> Self
+    where
+        T: SpringValue,
+        GetSpring: Fn(&mut State) -> &mut SpringState<T>,
+        GetTarget: Fn(&State) -> T,
+    {
+        <Self as Component<&State, &mut State>>::instantiate((smooth_time, get_spring, get_target))
+    }
+}
+
+impl<
+        State,
+        T: SpringValue,
+        GetSpring: Fn(&mut State) -> &mut SpringState<T>,
+        GetTarget: Fn(&State) -> T,
+    > Component<&State, &mut State> for Spring<GetSpring, GetTarget>
+{
+    type Input = (f32, GetSpring, GetTarget);
+
+    fn instantiate((smooth_time, get_spring, get_target): Self::Input) -> Self {
+        Self {
+            smooth_time,
+            get_spring,
+            get_target,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let target = (self.get_target)(state);
+        (self.get_spring)(state).update(target, self.smooth_time, context.get_delta());
+        state
+    }
+}
+
+/// Moves the value towards `target` by exponential smoothing, halving the distance every `half_life` seconds.
+///
+/// The result is the same no matter how the time is divided over the frames
+pub fn smooth_towards<T: SpringValue>(value: T, target: T, half_life: f32, delta: f32) -> T {
+    if half_life <= 0. {
+        return target;
+    }
+    let remaining = (-std::f32::consts::LN_2 * delta / half_life).exp();
+    target + (value - target) * remaining
+}
+
+/// Moves the value that `get_value` points to towards the target that `get_target` returns, see [smooth_towards].
+///
+/// It starts fast and slows down as it gets closer, without keeping track of a velocity
+pub struct SmoothFollow<GetValue, GetTarget> {
+    half_life: f32,
+    get_value: GetValue,
+    get_target: GetTarget,
+}
+
+impl<GetValue, GetTarget> SmoothFollow<GetValue, GetTarget> {
+    pub fn new<State, T>(half_life: f32, get_value: GetValue, get_target: GetTarget) -> Self
+    where
+        T: SpringValue,
+        GetValue: Fn(&mut State) -> &mut T,
+        GetTarget: Fn(&State) -> T,
+    {
+        <Self as Component<&State, &mut State>>::instantiate((half_life, get_value, get_target))
+    }
+}
+
+impl<State, T: SpringValue, GetValue: Fn(&mut State) -> &mut T, GetTarget: Fn(&State) -> T>
+    Component<&State, &mut State> for SmoothFollow<GetValue, GetTarget>
+{
+    type Input = (f32, GetValue, GetTarget);
+
+    fn instantiate((half_life, get_value, get_target): Self::Input) -> Self {
+        Self {
+            half_life,
+            get_value,
+            get_target,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let target = (self.get_target)(state);
+        let value = (self.get_value)(state);
+        *value = smooth_towards(*value, target, self.half_life, context.get_delta());
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::{vec2, Vec2};
+
+    use super::{smooth_towards, SmoothFollow, Spring, SpringState};
+    use crate::testing::TestHarness;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn spring_does_not_depend_on_the_frame_rate() {
+        let mut one_step = SpringState::new(0f32);
+        one_step.update(10., 0.5, 1.);
+        let mut many_steps = SpringState::new(0f32);
+        for _ in 0..60 {
+            many_steps.update(10., 0.5, 1. / 60.);
+        }
+        assert!(close(one_step.value, many_steps.value));
+        assert!(close(one_step.velocity, many_steps.velocity));
+    }
+
+    #[test]
+    fn spring_does_not_overshoot() {
+        let mut spring = SpringState::new(0f32);
+        let mut last = 0.;
+        for _ in 0..600 {
+            spring.update(10., 0.3, 1. / 60.);
+            assert!(spring.value <= 10.);
+            assert!(spring.value >= last);
+            last = spring.value;
+        }
+        assert!(close(spring.value, 10.));
+    }
+
+    #[test]
+    fn spring_without_smooth_time_snaps() {
+        let mut spring = SpringState {
+            value: vec2(1., 2.),
+            velocity: vec2(5., 5.),
+        };
+        spring.update(vec2(10., 20.), 0., 0.1);
+        assert_eq!(spring.value, vec2(10., 20.));
+        assert_eq!(spring.velocity, Vec2::ZERO);
+    }
+
+    #[test]
+    fn smoothing_does_not_depend_on_the_frame_rate() {
+        let one_step = smooth_towards(0f32, 10., 0.25, 1.);
+        let mut many_steps = 0f32;
+        for _ in 0..60 {
+            many_steps = smooth_towards(many_steps, 10., 0.25, 1. / 60.);
+        }
+        assert!(close(one_step, many_steps));
+        // every half life halves the distance
+        assert!(close(smooth_towards(0f32, 10., 1., 1.), 5.));
+    }
+
+    #[test]
+    fn smoothing_without_half_life_snaps() {
+        assert_eq!(smooth_towards(0f32, 10., 0., 0.1), 10.);
+        assert_eq!(smooth_towards(0f32, 10., -1., 0.1), 10.);
+    }
+
+    #[derive(Default)]
+    struct State {
+        spring: SpringState<f32>,
+        value: f32,
+        target: f32,
+    }
+
+    fn spring(state: &mut State) -> &mut SpringState<f32> {
+        &mut state.spring
+    }
+
+    fn value(state: &mut State) -> &mut f32 {
+        &mut state.value
+    }
+
+    fn target(state: &State) -> f32 {
+        state.target
+    }
+
+    #[test]
+    fn components_move_towards_the_target() {
+        let state = State {
+            target: 10.,
+            ..Default::default()
+        };
+        let mut harness = TestHarness::new(
+            state,
+            (
+                Spring::new(0.5, spring, target),
+                SmoothFollow::new(0.5, value, target),
+            ),
+        );
+        harness.frames(60, 1. / 60.);
+        let mut expected = SpringState::new(0f32);
+        expected.update(10., 0.5, 1.);
+        assert!(close(harness.state().spring.value, expected.value));
+        assert!(close(
+            harness.state().value,
+            smooth_towards(0., 10., 0.5, 1.)
+        ));
+    }
+}
@@ -0,0 +1,583 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{components::Context, Component};
+
+use super::{Lerp, TweenKind};
+
+/// How often a [TimelineNode::Repeat] plays its node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepeatCount {
+    Times(u32),
+    Forever,
+}
+
+/// A part of a timeline, which can be made out of other parts
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TimelineNode {
+    /// moves the value of `track` from `from` to `to`
+    Tween {
+        track: String,
+        from: f32,
+        to: f32,
+        duration: f32,
+        tween_kind: TweenKind,
+    },
+    /// does nothing for the given amount of seconds
+    Delay(f32),
+    /// plays the nodes one after another
+    Sequence(Vec<TimelineNode>),
+    /// plays the nodes at the same time, taking as long as the longest one
+    Parallel(Vec<TimelineNode>),
+    /// plays the node multiple times. With `yoyo` every other time gets played backwards
+    Repeat {
+        node: Box<TimelineNode>,
+        count: RepeatCount,
+        yoyo: bool,
+    },
+}
+
+impl TimelineNode {
+    pub fn tween(
+        track: impl Into<String>,
+        from: f32,
+        to: f32,
+        duration: f32,
+        tween_kind: TweenKind,
+    ) -> Self {
+        TimelineNode::Tween {
+            track: track.into(),
+            from,
+            to,
+            duration,
+            tween_kind,
+        }
+    }
+    pub fn delay(duration: f32) -> Self {
+        TimelineNode::Delay(duration)
+    }
+    pub fn sequence(nodes: impl IntoIterator<Item = TimelineNode>) -> Self {
+        TimelineNode::Sequence(nodes.into_iter().collect())
+    }
+    pub fn parallel(nodes: impl IntoIterator<Item = TimelineNode>) -> Self {
+        TimelineNode::Parallel(nodes.into_iter().collect())
+    }
+    /// plays this node the given amount of times
+    pub fn repeat(self, times: u32) -> Self {
+        self.repeat_with(RepeatCount::Times(times), false)
+    }
+    pub fn repeat_forever(self) -> Self {
+        self.repeat_with(RepeatCount::Forever, false)
+    }
+    /// plays this node the given amount of times, going back and forth
+    pub fn yoyo(self, times: u32) -> Self {
+        self.repeat_with(RepeatCount::Times(times), true)
+    }
+    pub fn yoyo_forever(self) -> Self {
+        self.repeat_with(RepeatCount::Forever, true)
+    }
+    fn repeat_with(self, count: RepeatCount, yoyo: bool) -> Self {
+        TimelineNode::Repeat {
+            node: Box::new(self),
+            count,
+            yoyo,
+        }
+    }
+    /// how long it takes to play this node in seconds, infinite if it repeats forever
+    pub fn duration(&self) -> f32 {
+        match self {
+            TimelineNode::Tween { duration, .. } | TimelineNode::Delay(duration) => {
+                duration.max(0.)
+            }
+            TimelineNode::Sequence(nodes) => nodes.iter().map(TimelineNode::duration).sum(),
+            TimelineNode::Parallel(nodes) => {
+                nodes.iter().map(TimelineNode::duration).fold(0., f32::max)
+            }
+            TimelineNode::Repeat { node, count, .. } => match count {
+                RepeatCount::Times(0) => 0.,
+                RepeatCount::Times(times) => node.duration() * *times as f32,
+                RepeatCount::Forever => f32::INFINITY,
+            },
+        }
+    }
+    /// When this node ends up looping forever, the time the loop starts at and how long a single loop takes.
+    ///
+    /// Used to keep the time of a [TimelineState] small, as an f32 that keeps growing loses precision
+    fn loop_period(&self) -> Option<(f32, f32)> {
+        match self {
+            TimelineNode::Repeat { node, count, yoyo } => {
+                let node_duration = node.duration();
+                if *count == RepeatCount::Times(0) {
+                    None
+                } else if node_duration <= 0. || !node_duration.is_finite() {
+                    node.loop_period()
+                } else if *count == RepeatCount::Forever {
+                    let period = if *yoyo {
+                        node_duration * 2.
+                    } else {
+                        node_duration
+                    };
+                    Some((0., period))
+                } else {
+                    None
+                }
+            }
+            TimelineNode::Sequence(nodes) => {
+                let mut start = 0.;
+                for node in nodes {
+                    let duration = node.duration();
+                    if !duration.is_finite() {
+                        return node
+                            .loop_period()
+                            .map(|(loop_start, period)| (start + loop_start, period));
+                    }
+                    start += duration;
+                }
+                None
+            }
+            TimelineNode::Parallel(nodes) => {
+                // the nodes that end look the same at any time after their end, so the loop starts once they all ended.
+                // The nodes that loop forever need to line up, which only works if the longest loop is a multiple of the others
+                let ended_at = nodes
+                    .iter()
+                    .map(TimelineNode::duration)
+                    .filter(|v| v.is_finite())
+                    .fold(0., f32::max);
+                let mut found: Option<(f32, f32)> = None;
+                for node in nodes.iter().filter(|v| !v.duration().is_finite()) {
+                    let (start, period) = node.loop_period()?;
+                    found = Some(match found {
+                        None => (start, period),
+                        Some((found_start, found_period)) => {
+                            let (short, long) = if period < found_period {
+                                (period, found_period)
+                            } else {
+                                (found_period, period)
+                            };
+                            let ratio = long / short;
+                            if (ratio - ratio.round()).abs() > 1e-4 {
+                                return None;
+                            }
+                            (found_start.max(start), long)
+                        }
+                    });
+                }
+                found.map(|(start, period)| (start.max(ended_at), period))
+            }
+            _ => None,
+        }
+    }
+    /// writes the value of every track this node has reached at the given time
+    fn evaluate(&self, time: f32, values: &mut TrackValues) {
+        match self {
+            TimelineNode::Tween {
+                track,
+                from,
+                to,
+                duration,
+                tween_kind,
+            } => {
+                let progress = if *duration <= 0. {
+                    if time >= 0. {
+                        1.
+                    } else {
+                        0.
+                    }
+                } else {
+                    (time / duration).clamp(0., 1.)
+                };
+                let value = from.lerp(to, tween_kind.ease(progress));
+                values.insert(track.clone(), value);
+            }
+            TimelineNode::Delay(_) => {}
+            TimelineNode::Sequence(nodes) => {
+                let mut start = 0.;
+                for (index, node) in nodes.iter().enumerate() {
+                    // nodes that haven't started yet would overwrite the tracks of earlier nodes with their starting values
+                    if index > 0 && time < start {
+                        break;
+                    }
+                    node.evaluate(time - start, values);
+                    start += node.duration();
+                }
+            }
+            TimelineNode::Parallel(nodes) => {
+                for node in nodes {
+                    node.evaluate(time, values)
+                }
+            }
+            TimelineNode::Repeat { node, yoyo, count } => {
+                if *count == RepeatCount::Times(0) {
+                    return;
+                }
+                let node_duration = node.duration();
+                if node_duration <= 0. || !node_duration.is_finite() {
+                    node.evaluate(time, values);
+                    return;
+                }
+                let total = self.duration();
+                let time = time.max(0.);
+                let (iteration, local_time) = if time >= total {
+                    ((total / node_duration).round() - 1., node_duration)
+                } else {
+                    ((time / node_duration).floor(), time % node_duration)
+                };
+                let local_time = if *yoyo && iteration % 2. == 1. {
+                    node_duration - local_time
+                } else {
+                    local_time
+                };
+                node.evaluate(local_time, values)
+            }
+        }
+    }
+}
+
+/// The values of the tracks of a timeline or an [AnimationClip](super::AnimationClip) at a single point in time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrackValues {
+    values: BTreeMap<String, f32>,
+}
+
+impl TrackValues {
+    /// the value of the given track, none if the timeline didn't reach any tween of that track yet
+    pub fn get(&self, track: &str) -> Option<f32> {
+        self.values.get(track).copied()
+    }
+    pub fn get_or(&self, track: &str, default: f32) -> f32 {
+        self.get(track).unwrap_or(default)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+    pub(crate) fn insert(&mut self, track: String, value: f32) {
+        self.values.insert(track, value);
+    }
+}
+
+/// A timeline together with how far it got, meant to live in the state
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimelineState {
+    timeline: TimelineNode,
+    time: f32,
+    playing: bool,
+    reversed: bool,
+}
+
+impl TimelineState {
+    /// creates a timeline that starts playing right away
+    pub fn new(timeline: TimelineNode) -> Self {
+        Self {
+            timeline,
+            time: 0.,
+            playing: true,
+            reversed: false,
+        }
+    }
+    /// creates the timeline without playing it
+    pub fn paused(mut self) -> Self {
+        self.playing = false;
+        self
+    }
+    pub fn timeline(&self) -> &TimelineNode {
+        &self.timeline
+    }
+    /// how far the timeline is in seconds.
+    ///
+    /// For a timeline that loops forever this wraps around once it passed a full loop
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+    pub fn duration(&self) -> f32 {
+        self.timeline.duration()
+    }
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+    /// true once the timeline reached its end, or its start when playing in reverse
+    pub fn is_finished(&self) -> bool {
+        if self.reversed {
+            self.time <= 0.
+        } else {
+            self.time >= self.duration()
+        }
+    }
+    pub fn play(&mut self) {
+        self.playing = true
+    }
+    pub fn pause(&mut self) {
+        self.playing = false
+    }
+    /// changes the direction the timeline plays in, without changing how far it is
+    pub fn set_reversed(&mut self, reversed: bool) {
+        self.reversed = reversed
+    }
+    pub fn reverse(&mut self) {
+        self.reversed = !self.reversed
+    }
+    /// jumps to the given time in seconds
+    pub fn seek(&mut self, time: f32) {
+        let time = time.clamp(0., self.duration());
+        self.time = match self.timeline.loop_period() {
+            // the end of a timeline that loops forever, like where a reversed one restarts, is the end of its first loop
+            Some((start, period)) if time.is_infinite() => start + period,
+            Some((start, period)) if time > start + period => start + (time - start) % period,
+            _ => time,
+        };
+    }
+    /// jumps back to the start, or to the end when playing in reverse
+    pub fn restart(&mut self) {
+        self.seek(if self.reversed { self.duration() } else { 0. });
+    }
+    /// moves the timeline forward by `delta` seconds, or backwards when playing in reverse
+    pub fn advance(&mut self, delta: f32) {
+        if !self.playing {
+            return;
+        }
+        let delta = if self.reversed { -delta } else { delta };
+        self.seek(self.time + delta)
+    }
+    /// the value of every track at the current time
+    pub fn values(&self) -> TrackValues {
+        let mut values = TrackValues::default();
+        self.timeline.evaluate(self.time, &mut values);
+        values
+    }
+    pub fn value(&self, track: &str) -> Option<f32> {
+        self.values().get(track)
+    }
+}
+
+/// Plays the [TimelineState] that `get_timeline` points to and calls `on_update` with the values of its tracks every process
+pub struct Timeline<GetTimeline, OnUpdate> {
+    get_timeline: GetTimeline,
+    on_update: OnUpdate,
+}
+
+impl<GetTimeline, OnUpdate> Timeline<GetTimeline, OnUpdate> {
+    pub fn new<State>(get_timeline: GetTimeline, on_update: OnUpdate) -> Self
+    where
+        GetTimeline: Fn(&mut State) -> &mut TimelineState,
+        OnUpdate: Fn(&TrackValues, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((get_timeline, on_update))
+    }
+}
+
+impl<
+        State,
+        GetTimeline: Fn(&mut State) -> &mut TimelineState,
+        OnUpdate: Fn(&TrackValues, &mut State),
+    > Component<&State, &mut State> for Timeline<GetTimeline, OnUpdate>
+{
+    type Input = (GetTimeline, OnUpdate);
+
+    fn instantiate((get_timeline, on_update): Self::Input) -> Self {
+        Self {
+            get_timeline,
+            on_update,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let timeline = (self.get_timeline)(state);
+        timeline.advance(context.get_delta());
+        let values = timeline.values();
+        (self.on_update)(&values, state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TimelineNode, TimelineState};
+    use crate::components::animation::TweenKind;
+
+    fn tween(track: &str, duration: f32) -> TimelineNode {
+        TimelineNode::tween(track, 0., 10., duration, TweenKind::Linear)
+    }
+
+    fn value_at(timeline: &TimelineNode, time: f32, track: &str) -> Option<f32> {
+        let mut state = TimelineState::new(timeline.clone());
+        state.seek(time);
+        state.value(track)
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(tween("x", 2.).duration(), 2.);
+        assert_eq!(TimelineNode::delay(-1.).duration(), 0.);
+        let sequence =
+            TimelineNode::sequence([tween("x", 1.), TimelineNode::delay(0.5), tween("y", 2.)]);
+        assert_eq!(sequence.duration(), 3.5);
+        let parallel = TimelineNode::parallel([tween("x", 1.), tween("y", 3.)]);
+        assert_eq!(parallel.duration(), 3.);
+        assert_eq!(tween("x", 1.5).repeat(3).duration(), 4.5);
+        assert_eq!(tween("x", 1.5).yoyo(2).duration(), 3.);
+        assert_eq!(tween("x", 1.).repeat(0).duration(), 0.);
+        assert_eq!(tween("x", 1.).repeat_forever().duration(), f32::INFINITY);
+    }
+
+    #[test]
+    fn sequence_plays_one_node_after_another() {
+        let sequence =
+            TimelineNode::sequence([tween("x", 1.), TimelineNode::delay(1.), tween("y", 2.)]);
+        assert_eq!(value_at(&sequence, 0.5, "x"), Some(5.));
+        assert_eq!(value_at(&sequence, 0.5, "y"), None);
+        assert_eq!(value_at(&sequence, 1.5, "x"), Some(10.));
+        assert_eq!(value_at(&sequence, 1.5, "y"), None);
+        assert_eq!(value_at(&sequence, 3., "y"), Some(5.));
+        assert_eq!(value_at(&sequence, 10., "y"), Some(10.));
+    }
+
+    #[test]
+    fn sequence_keeps_the_value_of_an_earlier_node_for_the_same_track() {
+        let sequence = TimelineNode::sequence([
+            tween("x", 1.),
+            TimelineNode::tween("x", 10., 20., 1., TweenKind::Linear),
+        ]);
+        assert_eq!(value_at(&sequence, 0.5, "x"), Some(5.));
+        assert_eq!(value_at(&sequence, 1.5, "x"), Some(15.));
+    }
+
+    #[test]
+    fn parallel_plays_nodes_at_the_same_time() {
+        let parallel = TimelineNode::parallel([tween("x", 1.), tween("y", 2.)]);
+        let mut state = TimelineState::new(parallel);
+        state.seek(0.5);
+        let values = state.values();
+        assert_eq!(values.get("x"), Some(5.));
+        assert_eq!(values.get("y"), Some(2.5));
+        state.seek(1.5);
+        let values = state.values();
+        assert_eq!(values.get("x"), Some(10.));
+        assert_eq!(values.get("y"), Some(7.5));
+    }
+
+    #[test]
+    fn repeat_starts_over_every_time() {
+        let repeat = tween("x", 1.).repeat(3);
+        assert_eq!(value_at(&repeat, 0.25, "x"), Some(2.5));
+        assert_eq!(value_at(&repeat, 1.25, "x"), Some(2.5));
+        assert_eq!(value_at(&repeat, 2.75, "x"), Some(7.5));
+        assert_eq!(value_at(&repeat, 3., "x"), Some(10.));
+    }
+
+    #[test]
+    fn yoyo_plays_every_other_time_backwards() {
+        let yoyo = tween("x", 1.).yoyo(2);
+        assert_eq!(value_at(&yoyo, 0.25, "x"), Some(2.5));
+        assert_eq!(value_at(&yoyo, 1.25, "x"), Some(7.5));
+        assert_eq!(value_at(&yoyo, 2., "x"), Some(0.));
+    }
+
+    #[test]
+    fn repeating_zero_times_writes_nothing() {
+        let repeat = tween("x", 1.).repeat(0);
+        assert_eq!(value_at(&repeat, 0., "x"), None);
+        assert_eq!(value_at(&repeat, 1., "x"), None);
+    }
+
+    #[test]
+    fn looping_forever_keeps_the_time_small() {
+        let mut state = TimelineState::new(tween("x", 1.).yoyo_forever());
+        for _ in 0..1001 {
+            state.advance(0.75);
+        }
+        assert_eq!(state.time(), 0.75);
+        assert_eq!(state.value("x"), Some(7.5));
+
+        let sequence = TimelineNode::sequence([tween("x", 1.), tween("y", 2.).repeat_forever()]);
+        let mut state = TimelineState::new(sequence);
+        state.seek(10.5);
+        assert_eq!(state.time(), 2.5);
+        assert_eq!(state.value("x"), Some(10.));
+        assert_eq!(state.value("y"), Some(7.5));
+    }
+
+    #[test]
+    fn reversed_restart_of_a_timeline_that_loops_forever() {
+        let mut state = TimelineState::new(tween("x", 1.).yoyo_forever());
+        state.set_reversed(true);
+        state.restart();
+        assert_eq!(state.time(), 2.);
+        assert_eq!(state.value("x"), Some(0.));
+        state.advance(0.5);
+        assert_eq!(state.time(), 1.5);
+        assert_eq!(state.value("x"), Some(5.));
+        state.advance(2.);
+        assert_eq!(state.time(), 0.);
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn parallel_with_a_loop_keeps_the_time_small() {
+        let parallel = TimelineNode::parallel([
+            tween("x", 3.),
+            tween("y", 1.).repeat_forever(),
+            tween("z", 1.).yoyo_forever(),
+        ]);
+        let mut state = TimelineState::new(parallel);
+        state.seek(100.25);
+        assert_eq!(state.time(), 4.25);
+        let values = state.values();
+        assert_eq!(values.get("x"), Some(10.));
+        assert_eq!(values.get("y"), Some(2.5));
+        assert_eq!(values.get("z"), Some(2.5));
+
+        // loops that never line up can't be wrapped
+        let parallel = TimelineNode::parallel([
+            tween("y", 1.).repeat_forever(),
+            tween("z", 1.5).repeat_forever(),
+        ]);
+        let mut state = TimelineState::new(parallel);
+        state.seek(100.);
+        assert_eq!(state.time(), 100.);
+    }
+
+    #[test]
+    fn advance_plays_until_the_end() {
+        let mut state = TimelineState::new(tween("x", 1.));
+        assert!(!state.is_finished());
+        state.advance(0.25);
+        assert_eq!(state.value("x"), Some(2.5));
+        state.advance(1.);
+        assert_eq!(state.time(), 1.);
+        assert!(state.is_finished());
+        assert_eq!(state.value("x"), Some(10.));
+    }
+
+    #[test]
+    fn reverse_playback() {
+        let mut state = TimelineState::new(tween("x", 1.));
+        state.seek(0.5);
+        state.reverse();
+        assert!(state.is_reversed());
+        assert!(!state.is_finished());
+        state.advance(0.25);
+        assert_eq!(state.value("x"), Some(2.5));
+        state.advance(1.);
+        assert_eq!(state.time(), 0.);
+        assert!(state.is_finished());
+        state.restart();
+        assert_eq!(state.time(), 1.);
+    }
+
+    #[test]
+    fn paused_timelines_do_not_move() {
+        let mut state = TimelineState::new(tween("x", 1.)).paused();
+        assert!(!state.is_playing());
+        state.advance(0.5);
+        assert_eq!(state.time(), 0.);
+        state.play();
+        state.advance(0.5);
+        assert_eq!(state.time(), 0.5);
+        state.pause();
+        state.advance(0.5);
+        assert_eq!(state.time(), 0.5);
+    }
+}
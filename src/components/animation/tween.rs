@@ -1,8 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{components::Context, Component};
 
 use super::animate_state::AnimateState;
 
-#[derive(Clone, Copy, Debug)]
+/// The curve a tween follows, see <https://easings.net> for what most of them look like.
+///
+/// This isn't `Copy` since 0.2, as [TweenKind::PiecewiseLinear] owns its points. Clone it instead
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TweenKind {
     Linear,
     EaseInSine,
@@ -35,10 +40,97 @@ pub enum TweenKind {
     EaseInBounce,
     EaseOutBounce,
     EaseInOutBounce,
+    /// a curve like the css `cubic-bezier` function, going through (0, 0), (x1, y1), (x2, y2) and (1, 1).
+    ///
+    /// Both x values should be between 0 and 1
+    CubicBezier(f32, f32, f32, f32),
+    /// jumps from 0 to 1 in the given amount of equal steps, like the css `steps` function
+    Steps(u32),
+    /// goes in straight lines between the given (x, y) points, which should be sorted by x.
+    ///
+    /// Before the first point and after the last the curve stays at their value
+    PiecewiseLinear(Vec<(f32, f32)>),
+    /// any other curve. Tweens using this can't be serialized
+    #[serde(skip)]
+    Custom(fn(f32) -> f32),
+}
+
+impl PartialEq for TweenKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TweenKind::CubicBezier(a1, b1, c1, d1), TweenKind::CubicBezier(a2, b2, c2, d2)) => {
+                (a1, b1, c1, d1) == (a2, b2, c2, d2)
+            }
+            (TweenKind::Steps(a), TweenKind::Steps(b)) => a == b,
+            (TweenKind::PiecewiseLinear(a), TweenKind::PiecewiseLinear(b)) => a == b,
+            (TweenKind::Custom(a), TweenKind::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let bezier = |a: f32, b: f32, t: f32| {
+        3. * a * (1. - t) * (1. - t) * t + 3. * b * (1. - t) * t * t + t * t * t
+    };
+    let derivative = |a: f32, b: f32, t: f32| {
+        3. * (1. - t) * (1. - t) * a + 6. * (1. - t) * t * (b - a) + 3. * t * t * (1. - b)
+    };
+    // newton's method is fast but can fail for steep curves, bisection always gets there
+    let mut t = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, t) - x;
+        if error.abs() < 1e-6 {
+            return bezier(y1, y2, t);
+        }
+        let slope = derivative(x1, x2, t);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        t -= error / slope;
+    }
+    let (mut low, mut high) = (0., 1.);
+    t = x;
+    for _ in 0..64 {
+        let found = bezier(x1, x2, t);
+        if (found - x).abs() < 1e-6 {
+            break;
+        }
+        if found < x {
+            low = t
+        } else {
+            high = t
+        }
+        t = (low + high) / 2.;
+    }
+    bezier(y1, y2, t)
+}
+
+fn piecewise_linear(points: &[(f32, f32)], x: f32) -> f32 {
+    let next = points.iter().position(|(point_x, _)| *point_x > x);
+    match next {
+        None => points.last().map(|(_, y)| *y).unwrap_or(x),
+        Some(0) => points[0].1,
+        Some(index) => {
+            let (x1, y1) = points[index - 1];
+            let (x2, y2) = points[index];
+            y1 + (y2 - y1) * (x - x1) / (x2 - x1)
+        }
+    }
 }
 
 impl TweenKind {
-    fn process(&self, x: f32) -> f32 {
+    /// Where the curve is at `x`.
+    ///
+    /// `x` goes from 0 at the start to 1 at the end. The result is 0 at the start and 1 at the end as well,
+    /// but some curves like [TweenKind::EaseInBack] or [TweenKind::EaseOutElastic] overshoot along the way
+    pub fn ease(&self, x: f32) -> f32 {
         match self {
             TweenKind::Linear => x,
             TweenKind::EaseInSine => 1. - f32::cos((x * std::f32::consts::PI) / 2.),
@@ -62,7 +154,7 @@ impl TweenKind {
                     1. - f32::powf(-2. * x + 2., 5.) / 2.
                 }
             }
-            TweenKind::EaseInCirc => f32::sqrt(1. - f32::powf(x, 2.)),
+            TweenKind::EaseInCirc => 1. - f32::sqrt(1. - f32::powf(x, 2.)),
             TweenKind::EaseOutCirc => f32::sqrt(1. - f32::powf(x - 1., 2.)),
             TweenKind::EaseInOutCirc => {
                 if x < 0.5 {
@@ -84,9 +176,9 @@ impl TweenKind {
             TweenKind::EaseOutElastic => {
                 let c4 = (2. * std::f32::consts::PI) / 3.;
 
-                if x < 0. {
+                if x <= 0. {
                     0.
-                } else if x > 1. {
+                } else if x >= 1. {
                     1.
                 } else {
                     f32::powf(2., -10. * x) * f32::sin((x * 10. - 0.75) * c4) + 1.
@@ -95,14 +187,14 @@ impl TweenKind {
             TweenKind::EaseInOutElastic => {
                 let c5 = (2. * std::f32::consts::PI) / 4.5;
 
-                if x < 0. {
+                if x <= 0. {
                     0.
-                } else if x > 1. {
+                } else if x >= 1. {
                     1.
                 } else if x < 0.5 {
                     -(f32::powf(2., 20. * x - 10.) * f32::sin((20. * x - 11.125) * c5)) / 2.
                 } else {
-                    (f32::powf(2., -20. * x + 10.) * f32::sin((20. * x - 11.125) * c5)) / 2.
+                    (f32::powf(2., -20. * x + 10.) * f32::sin((20. * x - 11.125) * c5)) / 2. + 1.
                 }
             }
             TweenKind::EaseInQuad => x * x,
@@ -138,9 +230,9 @@ impl TweenKind {
                 }
             }
             TweenKind::EaseInOutExpo => {
-                if x < 0. {
+                if x <= 0. {
                     0.
-                } else if x > 1. {
+                } else if x >= 1. {
                     1.
                 } else if x < 0.5 {
                     f32::powf(2., 20. * x - 10.) / 2.
@@ -170,7 +262,7 @@ impl TweenKind {
                     (f32::powf(2. * x - 2., 2.) * ((c2 + 1.) * (x * 2. - 2.) + c2) + 2.) / 2.
                 }
             }
-            TweenKind::EaseInBounce => 1. - &TweenKind::EaseOutBounce.process(1. - x),
+            TweenKind::EaseInBounce => 1. - TweenKind::EaseOutBounce.ease(1. - x),
             TweenKind::EaseOutBounce => {
                 let n1 = 7.5625;
                 let d1 = 2.75;
@@ -190,32 +282,205 @@ impl TweenKind {
             }
             TweenKind::EaseInOutBounce => {
                 if x < 0.5 {
-                    (1. - TweenKind::EaseOutBounce.process(1. - 2. * x)) / 2.
+                    (1. - TweenKind::EaseOutBounce.ease(1. - 2. * x)) / 2.
                 } else {
-                    (1. + TweenKind::EaseOutBounce.process(2. * x - 1.)) / 2.
+                    (1. + TweenKind::EaseOutBounce.ease(2. * x - 1.)) / 2.
                 }
             }
+            TweenKind::CubicBezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, x),
+            TweenKind::Steps(steps) => {
+                if *steps == 0 || x >= 1. {
+                    x.min(1.)
+                } else {
+                    ((x * *steps as f32).floor() / *steps as f32).max(0.)
+                }
+            }
+            TweenKind::PiecewiseLinear(points) => piecewise_linear(points, x),
+            TweenKind::Custom(func) => func(x),
         }
     }
 }
 
+/// The data a [Tween] runs on together with how far it got, meant to live in the state.
+///
+/// To change just a few fields, start from [TweenConfig::new] so new fields get a default too:
+/// ```
+/// use monad_quad::components::animation::{TweenConfig, TweenKind};
+///
+/// let config = TweenConfig {
+///     looping: true,
+///     speed: 2.,
+///     ..TweenConfig::new(0f32, 1., TweenKind::Linear)
+/// };
+/// assert_eq!(config.at_time, 0.);
+/// ```
+/// A reversed tween starts at its end, which a struct literal doesn't do for you.
+/// Use [TweenConfig::with_reversed], or call [TweenConfig::reset] after setting `reversed`
 #[derive(Clone, Debug)]
 pub struct TweenConfig<T> {
     pub tween_data: T,
     pub at_time: f32,
+    /// how long the tween takes at normal speed. A tween of 0 seconds or less jumps straight to its end
     pub time_in_seconds: f32,
+    /// a [TweenKind::Custom] can't be serialized, so neither can a state that holds one
     pub tween_kind: TweenKind,
     pub run: bool,
+    /// how fast the tween plays, 1 being normal speed
+    pub speed: f32,
+    /// Plays the tween from the end to the start.
+    ///
+    /// A tween at 0 that gets reversed is already finished, see [TweenConfig::with_reversed] to start at the end
+    pub reversed: bool,
+    /// starts over once the tween finished, instead of stopping
+    pub looping: bool,
+}
+
+impl<T> TweenConfig<T> {
+    /// creates a tween that starts running right away, once and at normal speed
+    pub fn new(tween_data: T, time_in_seconds: f32, tween_kind: TweenKind) -> Self {
+        Self {
+            tween_data,
+            at_time: 0.,
+            time_in_seconds,
+            tween_kind,
+            run: true,
+            speed: 1.,
+            reversed: false,
+            looping: false,
+        }
+    }
+    /// plays the tween from the end to the start, starting at the end
+    pub fn with_reversed(mut self) -> Self {
+        self.reversed = true;
+        self.reset();
+        self
+    }
+    /// true once the tween reached its end, or its start when it is reversed
+    pub fn is_finished(&self) -> bool {
+        if self.reversed {
+            self.at_time <= 0.
+        } else {
+            self.at_time >= 1.
+        }
+    }
+    /// jumps to the given point of the tween, between 0 and 1
+    pub fn seek(&mut self, at_time: f32) {
+        self.at_time = at_time.clamp(0., 1.)
+    }
+    /// starts the tween over, from the end if it is reversed
+    pub fn reset(&mut self) {
+        self.at_time = if self.reversed { 1. } else { 0. };
+        self.run = true;
+    }
+    /// changes the direction the tween plays in, without changing how far it is
+    pub fn reverse(&mut self) {
+        self.reversed = !self.reversed
+    }
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed
+    }
+}
+
+/// What happened to a tween during a process
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TweenEvent {
+    /// the tween reached its end and stopped
+    Completed,
+    /// the tween reached its end and started over, because it is looping
+    Looped,
 }
 
 struct AnimateData {
     max_seconds: f32,
+    speed: f32,
     at: f32,
 }
 
-pub struct Tween<Func> {
+/// Runs a [TweenConfig] and calls `func` with the eased value every process.
+///
+/// Use [Tween::with_on_event] to also get told when the tween completes or loops
+pub struct Tween<Func, OnEvent = ()> {
     child: AnimateState<fn(f32, &mut AnimateData)>,
     func: Func,
+    on_event: OnEvent,
+}
+
+impl<Func> Tween<Func> {
+    pub fn new<T>(func: Func) -> Self
+    where
+        Func: Fn(f32, &mut T),
+    {
+        <Self as Component<&TweenConfig<T>, &mut TweenConfig<T>>>::instantiate(func)
+    }
+}
+
+impl<Func, OnEvent> Tween<Func, OnEvent> {
+    /// calls `on_event` with the tween data every time the tween completes or loops
+    pub fn with_on_event<T, NewOnEvent>(self, on_event: NewOnEvent) -> Tween<Func, NewOnEvent>
+    where
+        NewOnEvent: Fn(TweenEvent, &mut T),
+    {
+        Tween {
+            child: self.child,
+            func: self.func,
+            on_event,
+        }
+    }
+    fn process_tween<T>(
+        &mut self,
+        context: &Context,
+        state: &mut TweenConfig<T>,
+        mut on_event: impl FnMut(&OnEvent, TweenEvent, &mut T),
+    ) where
+        Func: Fn(f32, &mut T),
+    {
+        if !state.run || (state.is_finished() && !state.looping) {
+            return;
+        }
+        if state.time_in_seconds <= 0. || !state.time_in_seconds.is_finite() {
+            // there is no time to divide by, so it is over right away. Looping would loop endlessly within a single frame
+            let was_finished = state.is_finished();
+            state.at_time = if state.reversed { 0. } else { 1. };
+            (self.func)(state.tween_kind.ease(state.at_time), &mut state.tween_data);
+            if !was_finished && !state.looping {
+                on_event(&self.on_event, TweenEvent::Completed, &mut state.tween_data);
+            }
+            return;
+        }
+        let mut config = AnimateData {
+            max_seconds: state.time_in_seconds,
+            speed: if state.reversed {
+                -state.speed
+            } else {
+                state.speed
+            },
+            at: state.at_time,
+        };
+        self.child.process(context, &mut config);
+        let mut events = Vec::new();
+        if state.looping && config.at.is_finite() {
+            while !state.reversed && config.at >= 1. {
+                config.at -= 1.;
+                events.push(TweenEvent::Looped);
+            }
+            while state.reversed && config.at <= 0. {
+                config.at += 1.;
+                events.push(TweenEvent::Looped);
+            }
+        } else if (!state.reversed && config.at >= 1.) || (state.reversed && config.at <= 0.) {
+            events.push(TweenEvent::Completed);
+        }
+        state.at_time = config.at.clamp(0., 1.);
+        let at = state.tween_kind.ease(state.at_time);
+        (self.func)(at, &mut state.tween_data);
+        for event in events {
+            on_event(&self.on_event, event, &mut state.tween_data);
+        }
+    }
+}
+
+fn update(delta: f32, at: &mut AnimateData) {
+    at.at += delta / at.max_seconds * at.speed
 }
 
 impl<T, Func: Fn(f32, &mut T)> Component<&TweenConfig<T>, &mut TweenConfig<T>> for Tween<Func> {
@@ -225,12 +490,10 @@ impl<T, Func: Fn(f32, &mut T)> Component<&TweenConfig<T>, &mut TweenConfig<T>> f
     where
         Self: Sized,
     {
-        fn update(delta: f32, at: &mut AnimateData) {
-            at.at += delta / at.max_seconds
-        }
         Self {
             child: AnimateState::instantiate(update),
             func: input,
+            on_event: (),
         }
     }
 
@@ -239,16 +502,286 @@ impl<T, Func: Fn(f32, &mut T)> Component<&TweenConfig<T>, &mut TweenConfig<T>> f
         context: &Context,
         state: &'c mut TweenConfig<T>,
     ) -> &'c mut TweenConfig<T> {
-        if state.run && state.at_time < 1. {
-            let mut config = AnimateData {
-                max_seconds: state.time_in_seconds,
-                at: state.at_time,
-            };
-            self.child.process(context, &mut config);
-            state.at_time = config.at;
-            let at = state.tween_kind.process(state.at_time);
-            (self.func)(at, &mut state.tween_data);
+        self.process_tween(context, state, |_, _, _| {});
+        state
+    }
+}
+
+impl<T, Func: Fn(f32, &mut T), OnEvent: Fn(TweenEvent, &mut T)>
+    Component<&TweenConfig<T>, &mut TweenConfig<T>> for Tween<Func, OnEvent>
+{
+    type Input = (Func, OnEvent);
+
+    fn instantiate((func, on_event): Self::Input) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            child: AnimateState::instantiate(update),
+            func,
+            on_event,
         }
+    }
+
+    fn process<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut TweenConfig<T>,
+    ) -> &'c mut TweenConfig<T> {
+        self.process_tween(context, state, |on_event, event, data| {
+            on_event(event, data)
+        });
         state
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Tween, TweenConfig, TweenEvent, TweenKind};
+    use crate::components::Component;
+    use crate::testing::TestHarness;
+
+    type Events = (f32, Vec<TweenEvent>);
+
+    fn set(at: f32, state: &mut Events) {
+        state.0 = at
+    }
+
+    fn push(event: TweenEvent, state: &mut Events) {
+        state.1.push(event)
+    }
+
+    #[test]
+    fn tween_reaches_the_end_and_stays_there() {
+        let mut harness = TestHarness::new(
+            TweenConfig::new(0f32, 1., TweenKind::Linear),
+            Tween::new(|at, v: &mut f32| *v = at),
+        );
+        harness.frame(0.25);
+        assert_eq!(harness.state().tween_data, 0.25);
+        harness.frames(3, 0.25);
+        assert_eq!(harness.state().tween_data, 1.);
+        assert!(harness.state().is_finished());
+        harness.frames(3, 0.25);
+        assert_eq!(harness.state().at_time, 1.);
+        assert_eq!(harness.state().tween_data, 1.);
+    }
+
+    #[test]
+    fn tween_does_not_move_while_not_running() {
+        let mut config = TweenConfig::new(0f32, 1., TweenKind::Linear);
+        config.run = false;
+        let mut harness = TestHarness::new(config, Tween::new(|at, v: &mut f32| *v = at));
+        harness.frames(3, 0.25);
+        assert_eq!(harness.state().at_time, 0.);
+        harness.state_mut().run = true;
+        harness.frame(0.25);
+        assert_eq!(harness.state().tween_data, 0.25);
+    }
+
+    #[test]
+    fn tween_without_duration_completes_once() {
+        let mut harness = TestHarness::new(
+            TweenConfig::new((0., Vec::new()), 0., TweenKind::Linear),
+            Tween::new(set as fn(_, &mut _)).with_on_event(push),
+        );
+        harness.frames(3, 0.25).frame(0.);
+        assert_eq!(
+            harness.state().tween_data,
+            (1., vec![TweenEvent::Completed])
+        );
+    }
+
+    #[test]
+    fn looping_tween_without_duration_stays_at_the_end() {
+        let config = TweenConfig {
+            looping: true,
+            ..TweenConfig::new((0., Vec::new()), 0., TweenKind::Linear)
+        };
+        let mut harness =
+            TestHarness::new(config, Tween::new(set as fn(_, &mut _)).with_on_event(push));
+        harness.frame(0.25).frame(0.);
+        assert_eq!(harness.state().at_time, 1.);
+        assert_eq!(harness.state().tween_data, (1., Vec::new()));
+    }
+
+    #[test]
+    fn looping_tween_starts_over() {
+        let config = TweenConfig {
+            looping: true,
+            ..TweenConfig::new((0., Vec::new()), 1., TweenKind::Linear)
+        };
+        let mut harness =
+            TestHarness::new(config, Tween::new(set as fn(_, &mut _)).with_on_event(push));
+        harness.frames(5, 0.25);
+        assert_eq!(harness.state().tween_data, (0.25, vec![TweenEvent::Looped]));
+    }
+
+    fn events_harness(
+        config: TweenConfig<Events>,
+    ) -> TestHarness<
+        TweenConfig<Events>,
+        impl for<'a> Component<&'a TweenConfig<Events>, &'a mut TweenConfig<Events>>,
+    > {
+        TestHarness::new(config, Tween::new(set as fn(_, &mut _)).with_on_event(push))
+    }
+
+    #[test]
+    fn tween_completes_once_at_the_end() {
+        let mut harness = events_harness(TweenConfig::new((0., Vec::new()), 1., TweenKind::Linear));
+        harness.frames(3, 0.25);
+        assert_eq!(harness.state().tween_data, (0.75, Vec::new()));
+        harness.frames(3, 0.25);
+        assert_eq!(
+            harness.state().tween_data,
+            (1., vec![TweenEvent::Completed])
+        );
+    }
+
+    #[test]
+    fn reversed_tween_plays_from_the_end() {
+        let config = TweenConfig::new((0., Vec::new()), 1., TweenKind::Linear).with_reversed();
+        assert_eq!(config.at_time, 1.);
+        let mut harness = events_harness(config);
+        harness.frame(0.25);
+        assert_eq!(harness.state().tween_data, (0.75, Vec::new()));
+        harness.frames(4, 0.25);
+        assert_eq!(
+            harness.state().tween_data,
+            (0., vec![TweenEvent::Completed])
+        );
+        assert!(harness.state().is_finished());
+
+        // reversing halfway turns around without jumping
+        let mut harness = events_harness(TweenConfig::new((0., Vec::new()), 1., TweenKind::Linear));
+        harness.frames(2, 0.25);
+        harness.state_mut().reverse();
+        harness.frame(0.25);
+        assert_eq!(harness.state().tween_data.0, 0.25);
+    }
+
+    #[test]
+    fn seek_and_speed() {
+        let mut harness = events_harness(TweenConfig::new((0., Vec::new()), 1., TweenKind::Linear));
+        harness.state_mut().seek(0.5);
+        harness.state_mut().set_speed(2.);
+        harness.frame(0.125);
+        assert_eq!(harness.state().tween_data.0, 0.75);
+        harness.state_mut().seek(2.);
+        assert_eq!(harness.state().at_time, 1.);
+        harness.state_mut().reset();
+        harness.frame(0.25);
+        assert_eq!(harness.state().tween_data.0, 0.5);
+    }
+
+    fn assert_eases(kind: &TweenKind, expected: &[(f32, f32)]) {
+        for (x, y) in expected {
+            let found = kind.ease(*x);
+            assert!(
+                (found - y).abs() < 1e-3,
+                "{kind:?} at {x} should be {y}, got {found}"
+            );
+        }
+    }
+
+    #[test]
+    fn eases_match_easings_net() {
+        // the values of the functions on https://easings.net at 0, 0.25, 0.5 and 1
+        let table = [
+            (TweenKind::Linear, [0., 0.25, 0.5, 1.]),
+            (TweenKind::EaseInSine, [0., 0.0761, 0.2929, 1.]),
+            (
+                TweenKind::EaseOutSine,
+                [0., 0.3827, std::f32::consts::FRAC_1_SQRT_2, 1.],
+            ),
+            (TweenKind::EaseInOutSine, [0., 0.1464, 0.5, 1.]),
+            (TweenKind::EaseInQuad, [0., 0.0625, 0.25, 1.]),
+            (TweenKind::EaseOutQuad, [0., 0.4375, 0.75, 1.]),
+            (TweenKind::EaseInOutQuad, [0., 0.125, 0.5, 1.]),
+            (TweenKind::EaseInCubic, [0., 0.0156, 0.125, 1.]),
+            (TweenKind::EaseOutCubic, [0., 0.5781, 0.875, 1.]),
+            (TweenKind::EaseInOutCubic, [0., 0.0625, 0.5, 1.]),
+            (TweenKind::EaseInQuart, [0., 0.0039, 0.0625, 1.]),
+            (TweenKind::EaseOutQuart, [0., 0.6836, 0.9375, 1.]),
+            (TweenKind::EaseInOutQuart, [0., 0.0312, 0.5, 1.]),
+            (TweenKind::EaseInQuint, [0., 0.001, 0.0312, 1.]),
+            (TweenKind::EaseOutQuint, [0., 0.7627, 0.9688, 1.]),
+            (TweenKind::EaseInOutQuint, [0., 0.0156, 0.5, 1.]),
+            (TweenKind::EaseInExpo, [0., 0.0055, 0.0312, 1.]),
+            (TweenKind::EaseOutExpo, [0., 0.8232, 0.9688, 1.]),
+            (TweenKind::EaseInOutExpo, [0., 0.0156, 0.5, 1.]),
+            (TweenKind::EaseInCirc, [0., 0.0318, 0.134, 1.]),
+            (TweenKind::EaseOutCirc, [0., 0.6614, 0.866, 1.]),
+            (TweenKind::EaseInOutCirc, [0., 0.067, 0.5, 1.]),
+            (TweenKind::EaseInBack, [0., -0.0641, -0.0877, 1.]),
+            (TweenKind::EaseOutBack, [0., 0.8174, 1.0877, 1.]),
+            (TweenKind::EaseInOutBack, [0., -0.0997, 0.5, 1.]),
+            (TweenKind::EaseInElastic, [0., -0.0055, -0.0156, 1.]),
+            (TweenKind::EaseOutElastic, [0., 0.9116, 1.0156, 1.]),
+            (TweenKind::EaseInOutElastic, [0., 0.012, 0.5, 1.]),
+            (TweenKind::EaseInBounce, [0., 0.0273, 0.2344, 1.]),
+            (TweenKind::EaseOutBounce, [0., 0.4727, 0.7656, 1.]),
+            (TweenKind::EaseInOutBounce, [0., 0.1172, 0.5, 1.]),
+        ];
+        for (kind, [start, quarter, half, end]) in table {
+            assert_eases(
+                &kind,
+                &[(0., start), (0.25, quarter), (0.5, half), (1., end)],
+            );
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_matches_css() {
+        // the css `ease` curve
+        let ease = TweenKind::CubicBezier(0.25, 0.1, 0.25, 1.);
+        assert_eases(
+            &ease,
+            &[
+                (0., 0.),
+                (0.25, 0.4085),
+                (0.5, 0.8024),
+                (0.75, 0.9605),
+                (1., 1.),
+            ],
+        );
+        let linear = TweenKind::CubicBezier(1. / 3., 1. / 3., 2. / 3., 2. / 3.);
+        assert_eases(&linear, &[(0.1, 0.1), (0.5, 0.5), (0.9, 0.9)]);
+        // steep curves need the bisection fallback
+        let steep = TweenKind::CubicBezier(1., 0., 0., 1.);
+        assert_eases(&steep, &[(0., 0.), (0.5, 0.5), (1., 1.)]);
+    }
+
+    #[test]
+    fn steps_jump_in_equal_steps() {
+        assert_eases(
+            &TweenKind::Steps(4),
+            &[
+                (0., 0.),
+                (0.2, 0.),
+                (0.25, 0.25),
+                (0.6, 0.5),
+                (0.99, 0.75),
+                (1., 1.),
+            ],
+        );
+        assert_eases(&TweenKind::Steps(0), &[(0.3, 0.3), (1., 1.)]);
+    }
+
+    #[test]
+    fn piecewise_linear_goes_through_the_points() {
+        let kind = TweenKind::PiecewiseLinear(vec![(0., 0.), (0.5, 1.), (1., 0.5)]);
+        assert_eases(
+            &kind,
+            &[
+                (-1., 0.),
+                (0.25, 0.5),
+                (0.5, 1.),
+                (0.75, 0.75),
+                (1., 0.5),
+                (2., 0.5),
+            ],
+        );
+        assert_eases(&TweenKind::PiecewiseLinear(Vec::new()), &[(0.4, 0.4)]);
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{components::Context, Component};
+
+use super::{Lerp, TweenKind};
+
+/// A value that moves from `from` to `to` over time, meant to live in the state
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tweened<T> {
+    pub from: T,
+    pub to: T,
+    pub time_in_seconds: f32,
+    /// a [TweenKind::Custom] can't be serialized, saving a state that holds one fails
+    pub tween_kind: TweenKind,
+    /// how far along the tween is, between 0 and 1
+    pub at_time: f32,
+    pub run: bool,
+}
+
+impl<T: Lerp> Tweened<T> {
+    /// creates a tween that starts running right away
+    pub fn new(from: T, to: T, time_in_seconds: f32, tween_kind: TweenKind) -> Self {
+        Self {
+            from,
+            to,
+            time_in_seconds,
+            tween_kind,
+            at_time: 0.,
+            run: true,
+        }
+    }
+    /// the value at the current point of the tween
+    pub fn value(&self) -> T {
+        self.from
+            .lerp(&self.to, self.tween_kind.ease(self.at_time.clamp(0., 1.)))
+    }
+    pub fn is_finished(&self) -> bool {
+        self.at_time >= 1.
+    }
+    /// moves the tween forward by `delta` seconds, if it is running
+    pub fn advance(&mut self, delta: f32) {
+        if !self.run || self.is_finished() {
+            return;
+        }
+        self.at_time = if self.time_in_seconds <= 0. {
+            1.
+        } else {
+            (self.at_time + delta / self.time_in_seconds).min(1.)
+        };
+    }
+    pub fn restart(&mut self) {
+        self.at_time = 0.;
+    }
+    /// starts a new tween from the current value towards `to`
+    pub fn retarget(&mut self, to: T) {
+        self.from = self.value();
+        self.to = to;
+        self.at_time = 0.;
+    }
+}
+
+/// Runs the [Tweened] that `get_tween` points to and writes its value into the state with `set_field` every process
+pub struct TweenField<GetTween, SetField> {
+    get_tween: GetTween,
+    set_field: SetField,
+}
+
+impl<GetTween, SetField> TweenField<GetTween, SetField> {
+    pub fn new<State, T>(get_tween: GetTween, set_field: SetField) -> Self
+    where
+        T: Lerp,
+        GetTween: Fn(&mut State) -> &mut Tweened<T>,
+        SetField: Fn(T, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((get_tween, set_field))
+    }
+}
+
+impl<State, T: Lerp, GetTween: Fn(&mut State) -> &mut Tweened<T>, SetField: Fn(T, &mut State)>
+    Component<&State, &mut State> for TweenField<GetTween, SetField>
+{
+    type Input = (GetTween, SetField);
+
+    fn instantiate((get_tween, set_field): Self::Input) -> Self {
+        Self {
+            get_tween,
+            set_field,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let tween = (self.get_tween)(state);
+        tween.advance(context.get_delta());
+        let value = tween.value();
+        (self.set_field)(value, state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TweenField, Tweened};
+    use crate::{components::animation::TweenKind, testing::TestHarness};
+
+    #[test]
+    fn advances_until_finished() {
+        let mut tween = Tweened::new(0f32, 10., 2., TweenKind::Linear);
+        tween.advance(1.);
+        assert_eq!(tween.value(), 5.);
+        assert!(!tween.is_finished());
+        tween.advance(5.);
+        assert_eq!(tween.value(), 10.);
+        assert!(tween.is_finished());
+    }
+
+    #[test]
+    fn zero_length_finishes_right_away() {
+        let mut tween = Tweened::new(0f32, 10., 0., TweenKind::Linear);
+        tween.advance(0.016);
+        assert!(tween.is_finished());
+        assert_eq!(tween.value(), 10.);
+        let mut tween = Tweened::new(0f32, 10., -1., TweenKind::Linear);
+        tween.advance(0.016);
+        assert_eq!(tween.value(), 10.);
+    }
+
+    #[test]
+    fn stopped_tween_does_not_move() {
+        let mut tween = Tweened::new(0f32, 10., 1., TweenKind::Linear);
+        tween.run = false;
+        tween.advance(0.5);
+        assert_eq!(tween.value(), 0.);
+    }
+
+    #[test]
+    fn retarget_continues_from_the_current_value() {
+        let mut tween = Tweened::new(0f32, 10., 1., TweenKind::Linear);
+        tween.advance(0.5);
+        tween.retarget(0.);
+        assert_eq!(tween.from, 5.);
+        assert_eq!(tween.value(), 5.);
+        tween.advance(0.5);
+        assert_eq!(tween.value(), 2.5);
+        tween.advance(0.5);
+        assert_eq!(tween.value(), 0.);
+    }
+
+    struct State {
+        tween: Tweened<f32>,
+        value: f32,
+    }
+
+    fn tween(state: &mut State) -> &mut Tweened<f32> {
+        &mut state.tween
+    }
+
+    #[test]
+    fn writes_the_value_into_the_state() {
+        let state = State {
+            tween: Tweened::new(0., 10., 1., TweenKind::Linear),
+            value: -1.,
+        };
+        let mut harness = TestHarness::new(
+            state,
+            TweenField::new(tween, |value, state: &mut State| state.value = value),
+        );
+        harness.frame(0.25);
+        assert_eq!(harness.state().value, 2.5);
+        harness.frames(10, 0.25);
+        assert_eq!(harness.state().value, 10.);
+    }
+}
@@ -4,10 +4,42 @@ use crate::{components::Context, Component};
 
 use super::{AsyncExecutor, AsyncState};
 
-pub struct OnLoaded<Mapper, Child> {
+/// Only runs its child once there is a value to run it with.
+///
+/// The child gets [Component::on_enter] once a value shows up and [Component::on_exit] if it goes away again
+pub struct OnLoaded<Mapper, Child, Async> {
     executor: AsyncExecutor,
     mapper: Mapper,
     child: Child,
+    active: Option<(Rc<RefCell<Async>>, bool)>,
+}
+impl<Mapper, Child, Async> OnLoaded<Mapper, Child, Async> {
+    fn switch_to<T>(
+        &mut self,
+        context: &Context,
+        state: &mut T,
+        to: Option<(Rc<RefCell<Async>>, bool)>,
+    ) where
+        Child: for<'b> Component<
+            &'b (Rc<RefCell<Async>>, bool, &'b T),
+            &'b mut (Rc<RefCell<Async>>, bool, &'b mut T),
+        >,
+    {
+        match (self.active.take(), &to) {
+            (None, None) => {}
+            // still active, only remember the latest value so on_exit gets to see it
+            (Some(_), Some(_)) => {}
+            (Some((value, is_loaded)), None) => {
+                self.child
+                    .on_exit(context, &mut (value, is_loaded, &mut *state));
+            }
+            (None, Some((value, is_loaded))) => {
+                self.child
+                    .on_enter(context, &mut (value.clone(), *is_loaded, &mut *state));
+            }
+        }
+        self.active = to;
+    }
 }
 impl<
         T,
@@ -17,7 +49,7 @@ impl<
             &'b (Rc<RefCell<Async>>, bool, &'b T),
             &'b mut (Rc<RefCell<Async>>, bool, &'b mut T),
         >,
-    > Component<&T, &mut T> for OnLoaded<Mapper, Child>
+    > Component<&T, &mut T> for OnLoaded<Mapper, Child, Async>
 {
     type Input = (Mapper, Child);
 
@@ -29,6 +61,7 @@ impl<
             executor: AsyncExecutor,
             mapper,
             child,
+            active: None,
         }
     }
 
@@ -40,10 +73,14 @@ impl<
             &mut task,
         );
         let (is_loaded, value) = match task {
-            AsyncState::Unloaded | AsyncState::Loading(None, _) => return state,
+            AsyncState::Unloaded | AsyncState::Loading(None, _) => {
+                self.switch_to(context, state, None);
+                return state;
+            }
             AsyncState::Loading(Some(x), _) => (false, x),
             AsyncState::Loaded(x) => (true, x),
         };
+        self.switch_to(context, state, Some((value.clone(), is_loaded)));
         self.child.process(context, &mut (value, is_loaded, state));
         state
     }
@@ -67,12 +104,27 @@ impl<
         let task = (self.mapper)(state);
         let (is_loaded, value) = match task {
             AsyncState::Unloaded | AsyncState::Loading(None, _) => {
+                self.switch_to(context, state, None);
                 return state;
             }
             AsyncState::Loading(Some(x), _) => (false, x),
             AsyncState::Loaded(x) => (true, x),
         };
+        self.switch_to(context, state, Some((value.clone(), is_loaded)));
         self.child.ui(context, ui, &mut (value, is_loaded, state));
         state
     }
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut T) -> &'c mut T {
+        let active = match (self.mapper)(state) {
+            AsyncState::Unloaded | AsyncState::Loading(None, _) => None,
+            AsyncState::Loading(Some(x), _) => Some((x, false)),
+            AsyncState::Loaded(x) => Some((x, true)),
+        };
+        self.switch_to(context, state, active);
+        state
+    }
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut T) -> &'c mut T {
+        self.switch_to(context, state, None);
+        state
+    }
 }
@@ -16,6 +16,7 @@ impl<Async, OutState, Child: for<'a> Component<&'a OutState, &'a mut OutState>>
     }
     /// Configure the function needed to go from State A to the properties needed for Component B
 
+    #[allow(clippy::empty_line_after_doc_comments)]
     pub fn map_in<StateIn, Func: Fn(Rc<RefCell<Async>>, bool, &StateIn) -> OutState>(
         func: Func,
     ) -> MapInto<Async, StateIn, OutState, Child, Func> {
@@ -128,6 +129,26 @@ impl<
         (self.updater)(mapped_state, state.2);
         state
     }
+    fn on_enter<'c, 'd>(
+        &mut self,
+        context: &Context,
+        state: &'c mut (Rc<RefCell<Async>>, bool, &'d mut FullStateIn),
+    ) -> &'c mut (Rc<RefCell<Async>>, bool, &'d mut FullStateIn) {
+        let mut mapped_state = (self.mapper)(state.0.to_owned(), state.1, state.2);
+        self.child.on_enter(context, &mut mapped_state);
+        (self.updater)(mapped_state, state.2);
+        state
+    }
+    fn on_exit<'c, 'd>(
+        &mut self,
+        context: &Context,
+        state: &'c mut (Rc<RefCell<Async>>, bool, &'d mut FullStateIn),
+    ) -> &'c mut (Rc<RefCell<Async>>, bool, &'d mut FullStateIn) {
+        let mut mapped_state = (self.mapper)(state.0.to_owned(), state.1, state.2);
+        self.child.on_exit(context, &mut mapped_state);
+        (self.updater)(mapped_state, state.2);
+        state
+    }
 }
 impl<MapFunc, Updater, Child> AsyncSelector<MapFunc, Updater, Child> {
     pub fn new(map: MapFunc, updater: Updater, child: Child) -> Self {
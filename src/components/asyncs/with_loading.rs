@@ -4,14 +4,26 @@ use crate::{components::Context, Component};
 
 use super::{AsyncExecutor, AsyncState};
 
-pub struct WithLoading<Mapper, LoadedChild, LoadingChild> {
+enum ActiveChild<Async> {
+    Nothing,
+    Loading,
+    Loaded(Rc<RefCell<Async>>, bool),
+}
+
+/// Renders `loading_child` until there is a value to render `loaded_child` with.
+///
+/// Switching between the two calls [Component::on_exit] on the old child and [Component::on_enter] on the new one
+pub struct WithLoading<Mapper, LoadedChild, LoadingChild, Async> {
     executor: AsyncExecutor,
     mapper: Mapper,
     loaded_child: LoadedChild,
     loading_child: LoadingChild,
+    active: ActiveChild<Async>,
 }
-impl<Mapper, LoadedChild, LoadingChild> WithLoading<Mapper, LoadedChild, LoadingChild> {
-    pub fn new<T, Async>(map: Mapper, loaded_chid: LoadedChild, loading_child: LoadingChild) -> Self
+impl<Mapper, LoadedChild, LoadingChild, Async>
+    WithLoading<Mapper, LoadedChild, LoadingChild, Async>
+{
+    pub fn new<T>(map: Mapper, loaded_chid: LoadedChild, loading_child: LoadingChild) -> Self
     where
         Mapper: Fn(&T) -> AsyncState<Async>,
         LoadedChild: for<'b> Component<
@@ -22,6 +34,46 @@ impl<Mapper, LoadedChild, LoadingChild> WithLoading<Mapper, LoadedChild, Loading
     {
         Self::instantiate((map, loaded_chid, loading_child))
     }
+    fn switch_to<T>(&mut self, context: &Context, state: &mut T, to: ActiveChild<Async>)
+    where
+        LoadedChild: for<'b> Component<
+            &'b (Rc<RefCell<Async>>, bool, &'b T),
+            &'b mut (Rc<RefCell<Async>>, bool, &'b mut T),
+        >,
+        LoadingChild: for<'b> Component<&'b T, &'b mut T>,
+    {
+        match (&self.active, &to) {
+            (ActiveChild::Nothing, ActiveChild::Nothing)
+            | (ActiveChild::Loading, ActiveChild::Loading) => return,
+            (ActiveChild::Loaded(_, _), ActiveChild::Loaded(_, _)) => {
+                // still the same child, only remember the latest value so on_exit gets to see it
+                self.active = to;
+                return;
+            }
+            _ => {}
+        }
+        match std::mem::replace(&mut self.active, ActiveChild::Nothing) {
+            ActiveChild::Nothing => {}
+            ActiveChild::Loading => {
+                self.loading_child.on_exit(context, state);
+            }
+            ActiveChild::Loaded(value, is_loaded) => {
+                self.loaded_child
+                    .on_exit(context, &mut (value, is_loaded, &mut *state));
+            }
+        }
+        match &to {
+            ActiveChild::Nothing => {}
+            ActiveChild::Loading => {
+                self.loading_child.on_enter(context, state);
+            }
+            ActiveChild::Loaded(value, is_loaded) => {
+                self.loaded_child
+                    .on_enter(context, &mut (value.clone(), *is_loaded, &mut *state));
+            }
+        }
+        self.active = to;
+    }
 }
 impl<
         T,
@@ -32,7 +84,7 @@ impl<
             &'b mut (Rc<RefCell<Async>>, bool, &'b mut T),
         >,
         LoadingChild: for<'b> Component<&'b T, &'b mut T>,
-    > Component<&T, &mut T> for WithLoading<Mapper, LoadedChild, LoadingChild>
+    > Component<&T, &mut T> for WithLoading<Mapper, LoadedChild, LoadingChild, Async>
 {
     type Input = (Mapper, LoadedChild, LoadingChild);
 
@@ -45,6 +97,7 @@ impl<
             mapper,
             loaded_child,
             loading_child,
+            active: ActiveChild::Nothing,
         }
     }
 
@@ -57,11 +110,17 @@ impl<
         );
         let (is_loaded, value) = match task {
             AsyncState::Unloaded | AsyncState::Loading(None, _) => {
-                return self.loading_child.process(context, state)
+                self.switch_to(context, state, ActiveChild::Loading);
+                return self.loading_child.process(context, state);
             }
             AsyncState::Loading(Some(x), _) => (false, x),
             AsyncState::Loaded(x) => (true, x),
         };
+        self.switch_to(
+            context,
+            state,
+            ActiveChild::Loaded(value.clone(), is_loaded),
+        );
         self.loaded_child
             .process(context, &mut (value, is_loaded, state));
         state
@@ -87,13 +146,32 @@ impl<
         let task = (self.mapper)(state);
         let (is_loaded, value) = match task {
             AsyncState::Unloaded | AsyncState::Loading(None, _) => {
-                return self.loading_child.ui(context, ui, state)
+                self.switch_to(context, state, ActiveChild::Loading);
+                return self.loading_child.ui(context, ui, state);
             }
             AsyncState::Loading(Some(x), _) => (false, x),
             AsyncState::Loaded(x) => (true, x),
         };
+        self.switch_to(
+            context,
+            state,
+            ActiveChild::Loaded(value.clone(), is_loaded),
+        );
         self.loaded_child
             .ui(context, ui, &mut (value, is_loaded, state));
         state
     }
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut T) -> &'c mut T {
+        let active = match (self.mapper)(state) {
+            AsyncState::Unloaded | AsyncState::Loading(None, _) => ActiveChild::Loading,
+            AsyncState::Loading(Some(x), _) => ActiveChild::Loaded(x, false),
+            AsyncState::Loaded(x) => ActiveChild::Loaded(x, true),
+        };
+        self.switch_to(context, state, active);
+        state
+    }
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut T) -> &'c mut T {
+        self.switch_to(context, state, ActiveChild::Nothing);
+        state
+    }
 }
@@ -0,0 +1,130 @@
+use std::cell::RefCell;
+
+use macroquad::{
+    prelude::{set_camera, set_default_camera, vec2, Camera2D, Color, Rect, Vec2, BLACK, WHITE},
+    shapes::draw_rectangle,
+    text::draw_text,
+    texture::{
+        draw_texture_ex, render_target, DrawTextureParams, FilterMode, RenderTarget, Texture2D,
+    },
+    ui::root_ui,
+    window::{clear_background, screen_height, screen_width},
+};
+
+use super::Backend;
+
+struct ViewportTarget {
+    size: Vec2,
+    camera: Camera2D,
+    render_target: RenderTarget,
+}
+
+impl ViewportTarget {
+    fn new(size: Vec2) -> Self {
+        let render_target = render_target(size.x as u32, size.y as u32);
+        render_target.texture.set_filter(FilterMode::Linear);
+        let mut camera = Camera2D::from_display_rect(Rect::new(0., 0., size.x, size.y));
+        camera.render_target = Some(render_target.clone());
+        Self {
+            size,
+            camera,
+            render_target,
+        }
+    }
+}
+
+/// Draws everything to the macroquad window
+#[derive(Default)]
+pub struct MacroquadBackend {
+    active_viewports: RefCell<Vec<ViewportTarget>>,
+    unused_viewports: RefCell<Vec<ViewportTarget>>,
+}
+
+impl MacroquadBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Backend for MacroquadBackend {
+    fn window_size(&self) -> Vec2 {
+        vec2(screen_width(), screen_height())
+    }
+    fn clear_background(&self, color: Color) {
+        clear_background(color)
+    }
+    fn draw_rectangle(&self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        draw_rectangle(x, y, w, h, color)
+    }
+    fn draw_text(&self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+        draw_text(text, x, y, font_size, color);
+    }
+    fn draw_texture(
+        &self,
+        texture: &Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        params: &DrawTextureParams,
+    ) {
+        draw_texture_ex(texture, x, y, color, params.clone())
+    }
+    #[cfg(feature = "macroquad-tiled")]
+    fn draw_tiles(
+        &self,
+        map: &macroquad_tiled::Map,
+        layer: &str,
+        dest: Rect,
+        source: Option<Rect>,
+    ) {
+        map.draw_tiles(layer, dest, source);
+        let mut gl = unsafe { macroquad::window::get_internal_gl() };
+        gl.flush()
+    }
+    fn set_default_material(&self) {
+        macroquad::material::gl_use_default_material()
+    }
+    fn begin_viewport(&self, size: Vec2) {
+        let mut unused = self.unused_viewports.borrow_mut();
+        let target = match unused.iter().position(|v| v.size == size) {
+            Some(index) => unused.swap_remove(index),
+            None => ViewportTarget::new(size),
+        };
+        set_camera(&target.camera);
+        self.active_viewports.borrow_mut().push(target);
+    }
+    fn end_viewport(&self) {
+        let mut active = self.active_viewports.borrow_mut();
+        let target = match active.pop() {
+            Some(x) => x,
+            None => return,
+        };
+        let parent_size = match active.last() {
+            Some(parent) => {
+                set_camera(&parent.camera);
+                parent.size
+            }
+            None => {
+                set_default_camera();
+                clear_background(BLACK);
+                self.window_size()
+            }
+        };
+        let scale = f32::min(parent_size.x / target.size.x, parent_size.y / target.size.y);
+        draw_texture_ex(
+            &target.render_target.texture,
+            (parent_size.x - (target.size.x * scale)) * 0.5,
+            (parent_size.y - (target.size.y * scale)) * 0.5,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(target.size * scale),
+                flip_y: true, // Must flip y otherwise 'render_target' will be upside down
+                ..Default::default()
+            },
+        );
+        self.unused_viewports.borrow_mut().push(target);
+    }
+    fn with_ui(&self, func: &mut dyn FnMut(&mut macroquad::ui::Ui)) {
+        func(&mut root_ui())
+    }
+}
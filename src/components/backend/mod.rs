@@ -0,0 +1,49 @@
+use macroquad::{
+    prelude::{Color, Vec2},
+    texture::{DrawTextureParams, Texture2D},
+};
+
+mod macroquad_backend;
+mod recording;
+
+pub use macroquad_backend::MacroquadBackend;
+pub use recording::{DrawCall, RecordingBackend};
+
+/// The thing that [Context](crate::components::Context) sends every draw call to.
+///
+/// By default this is [MacroquadBackend], which draws to the window.
+/// [RecordingBackend] can be used instead to run a scene tree without a window.
+pub trait Backend {
+    /// The size of the window that is being drawn to
+    fn window_size(&self) -> Vec2;
+    fn clear_background(&self, color: Color);
+    fn draw_rectangle(&self, x: f32, y: f32, w: f32, h: f32, color: Color);
+    fn draw_text(&self, text: &str, x: f32, y: f32, font_size: f32, color: Color);
+    /// Draws (a part of) the texture with its top left corner at the given location, multiplying its colors with `color`
+    fn draw_texture(
+        &self,
+        texture: &Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        params: &DrawTextureParams,
+    );
+    /// Draws a layer of a tiled map into `dest`. `source` is the part of the map to draw in tiles, the whole map if none
+    #[cfg(feature = "macroquad-tiled")]
+    fn draw_tiles(
+        &self,
+        map: &macroquad_tiled::Map,
+        layer: &str,
+        dest: macroquad::prelude::Rect,
+        source: Option<macroquad::prelude::Rect>,
+    );
+    fn set_default_material(&self);
+    /// Everything drawn after this call ends up in a separate target of the given size, until [Backend::end_viewport] is called
+    fn begin_viewport(&self, size: Vec2);
+    /// Draws the target started by the last call to [Backend::begin_viewport], scaled to fit on whatever it is nested in
+    fn end_viewport(&self);
+    /// Runs the given function with the ui that components should draw to.
+    ///
+    /// Macroquad's ui needs a window, so backends without one don't call the function at all.
+    fn with_ui(&self, func: &mut dyn FnMut(&mut macroquad::ui::Ui));
+}
@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+
+use macroquad::{
+    prelude::{Color, Rect, Vec2},
+    texture::{DrawTextureParams, Texture2D},
+};
+
+use super::Backend;
+
+/// A single call made to a [Backend]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCall {
+    ClearBackground(Color),
+    Rectangle {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        color: Color,
+    },
+    Text {
+        text: String,
+        x: f32,
+        y: f32,
+        font_size: f32,
+        color: Color,
+    },
+    Texture {
+        texture: Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        dest_size: Option<Vec2>,
+        source: Option<Rect>,
+        rotation: f32,
+        flip_x: bool,
+        flip_y: bool,
+        pivot: Option<Vec2>,
+    },
+    #[cfg(feature = "macroquad-tiled")]
+    Tiles {
+        layer: String,
+        dest: Rect,
+        source: Option<Rect>,
+    },
+    SetDefaultMaterial,
+    BeginViewport(Vec2),
+    EndViewport,
+}
+
+/// A backend that doesn't need a window, it only remembers what it was asked to draw.
+///
+/// Useful to run scene trees in tests or on machines without a display.
+pub struct RecordingBackend {
+    window_size: Vec2,
+    draw_calls: RefCell<Vec<DrawCall>>,
+}
+
+impl RecordingBackend {
+    /// creates a new backend that pretends to draw to a window of the given size
+    pub fn new(window_size: Vec2) -> Self {
+        Self {
+            window_size,
+            draw_calls: RefCell::new(Vec::new()),
+        }
+    }
+    pub fn set_window_size(&mut self, window_size: Vec2) {
+        self.window_size = window_size
+    }
+    /// All the draw calls made since the last call to [RecordingBackend::take_draw_calls]
+    pub fn draw_calls(&self) -> Vec<DrawCall> {
+        self.draw_calls.borrow().clone()
+    }
+    /// Returns the recorded draw calls and starts recording from scratch
+    pub fn take_draw_calls(&self) -> Vec<DrawCall> {
+        self.draw_calls.take()
+    }
+    fn record(&self, call: DrawCall) {
+        self.draw_calls.borrow_mut().push(call)
+    }
+}
+
+impl Backend for RecordingBackend {
+    fn window_size(&self) -> Vec2 {
+        self.window_size
+    }
+    fn clear_background(&self, color: Color) {
+        self.record(DrawCall::ClearBackground(color))
+    }
+    fn draw_rectangle(&self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.record(DrawCall::Rectangle { x, y, w, h, color })
+    }
+    fn draw_text(&self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+        self.record(DrawCall::Text {
+            text: text.to_owned(),
+            x,
+            y,
+            font_size,
+            color,
+        })
+    }
+    fn draw_texture(
+        &self,
+        texture: &Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        params: &DrawTextureParams,
+    ) {
+        self.record(DrawCall::Texture {
+            texture: texture.clone(),
+            x,
+            y,
+            color,
+            dest_size: params.dest_size,
+            source: params.source,
+            rotation: params.rotation,
+            flip_x: params.flip_x,
+            flip_y: params.flip_y,
+            pivot: params.pivot,
+        })
+    }
+    #[cfg(feature = "macroquad-tiled")]
+    fn draw_tiles(&self, _: &macroquad_tiled::Map, layer: &str, dest: Rect, source: Option<Rect>) {
+        self.record(DrawCall::Tiles {
+            layer: layer.to_owned(),
+            dest,
+            source,
+        })
+    }
+    fn set_default_material(&self) {
+        self.record(DrawCall::SetDefaultMaterial)
+    }
+    fn begin_viewport(&self, size: Vec2) {
+        self.record(DrawCall::BeginViewport(size))
+    }
+    fn end_viewport(&self) {
+        self.record(DrawCall::EndViewport)
+    }
+    fn with_ui(&self, _: &mut dyn FnMut(&mut macroquad::ui::Ui)) {}
+}
@@ -0,0 +1,257 @@
+use super::{Component, Context};
+
+/// Here to help combine multiple components into 1
+///
+/// Will run process and render on each child in their respective order
+impl<RenderState: Clone, ProcessState, Comp: Component<RenderState, ProcessState>>
+    Component<RenderState, ProcessState> for (Comp,)
+{
+    fn process(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.0.process(context, state)
+    }
+
+    fn render(&self, context: &Context, props: RenderState) {
+        self.0.render(context, props)
+    }
+
+    type Input = Comp::Input;
+
+    fn instantiate(input: Self::Input) -> Self {
+        (Comp::instantiate(input),)
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: ProcessState,
+    ) -> ProcessState {
+        self.0.ui(context, ui, state)
+    }
+
+    fn on_enter(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.0.on_enter(context, state)
+    }
+
+    fn on_exit(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.0.on_exit(context, state)
+    }
+}
+
+/// implements [Component] for a tuple, the last child gets listed separately so it can get the props without cloning them
+macro_rules! impl_component_for_tuple {
+    ($($comp:ident $index:tt),+ ; $last:ident $last_index:tt) => {
+        /// Here to help combine multiple components into 1
+        ///
+        /// Will run process and render on each child in their respective order
+        impl<
+                RenderState: Clone,
+                ProcessState,
+                $($comp: Component<RenderState, ProcessState>,)+
+                $last: Component<RenderState, ProcessState>,
+            > Component<RenderState, ProcessState> for ($($comp,)+ $last)
+        {
+            fn process(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+                $(let state = self.$index.process(context, state);)+
+                self.$last_index.process(context, state)
+            }
+
+            fn render(&self, context: &Context, props: RenderState) {
+                $(self.$index.render(context, props.clone());)+
+                self.$last_index.render(context, props)
+            }
+
+            type Input = ($($comp::Input,)+ $last::Input);
+
+            fn instantiate(input: Self::Input) -> Self {
+                ($($comp::instantiate(input.$index),)+ $last::instantiate(input.$last_index))
+            }
+
+            fn ui<'c>(
+                &mut self,
+                context: &Context,
+                ui: &mut macroquad::ui::Ui,
+                state: ProcessState,
+            ) -> ProcessState {
+                $(let state = self.$index.ui(context, ui, state);)+
+                self.$last_index.ui(context, ui, state)
+            }
+
+            fn on_enter(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+                $(let state = self.$index.on_enter(context, state);)+
+                self.$last_index.on_enter(context, state)
+            }
+
+            fn on_exit(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+                $(let state = self.$index.on_exit(context, state);)+
+                self.$last_index.on_exit(context, state)
+            }
+        }
+    };
+}
+
+impl_component_for_tuple!(Comp 0; Comp2 1);
+impl_component_for_tuple!(Comp 0, Comp2 1; Comp3 2);
+impl_component_for_tuple!(Comp 0, Comp2 1, Comp3 2; Comp4 3);
+impl_component_for_tuple!(Comp 0, Comp2 1, Comp3 2, Comp4 3; Comp5 4);
+impl_component_for_tuple!(Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4; Comp6 5);
+impl_component_for_tuple!(Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5; Comp7 6);
+impl_component_for_tuple!(Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6; Comp8 7);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7; Comp9 8
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8; Comp10 9
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9;
+    Comp11 10
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10; Comp12 11
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11; Comp13 12
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12; Comp14 13
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12, Comp14 13; Comp15 14
+);
+impl_component_for_tuple!(
+    Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12, Comp14 13, Comp15 14; Comp16 15
+);
+
+/// Runs process and render on every child, in order.
+///
+/// Useful when there is a list of children that all have the same type
+impl<RenderState: Clone, ProcessState, Comp: Component<RenderState, ProcessState>>
+    Component<RenderState, ProcessState> for Vec<Comp>
+{
+    type Input = Vec<Comp::Input>;
+
+    fn instantiate(input: Self::Input) -> Self {
+        input.into_iter().map(Comp::instantiate).collect()
+    }
+
+    fn process(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.process(context, state))
+    }
+
+    fn render(&self, context: &Context, props: RenderState) {
+        for child in self {
+            child.render(context, props.clone())
+        }
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: ProcessState,
+    ) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.ui(context, ui, state))
+    }
+
+    fn on_enter(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.on_enter(context, state))
+    }
+
+    fn on_exit(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.on_exit(context, state))
+    }
+}
+
+/// Runs process and render on every child, in order.
+impl<
+        RenderState: Clone,
+        ProcessState,
+        Comp: Component<RenderState, ProcessState>,
+        const N: usize,
+    > Component<RenderState, ProcessState> for [Comp; N]
+{
+    type Input = [Comp::Input; N];
+
+    fn instantiate(input: Self::Input) -> Self {
+        input.map(Comp::instantiate)
+    }
+
+    fn process(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.process(context, state))
+    }
+
+    fn render(&self, context: &Context, props: RenderState) {
+        for child in self {
+            child.render(context, props.clone())
+        }
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: ProcessState,
+    ) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.ui(context, ui, state))
+    }
+
+    fn on_enter(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.on_enter(context, state))
+    }
+
+    fn on_exit(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.iter_mut()
+            .fold(state, |state, child| child.on_exit(context, state))
+    }
+}
+
+/// Allows boxed components, including `Box<dyn Component<...>>`, to be used as children.
+///
+/// As a trait object can't be instantiated, the box itself is the input
+impl<RenderState: Clone, ProcessState, Comp: Component<RenderState, ProcessState> + ?Sized>
+    Component<RenderState, ProcessState> for Box<Comp>
+{
+    type Input = Box<Comp>;
+
+    fn instantiate(input: Self::Input) -> Self {
+        input
+    }
+
+    fn process(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.as_mut().process(context, state)
+    }
+
+    fn render(&self, context: &Context, props: RenderState) {
+        self.as_ref().render(context, props)
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: ProcessState,
+    ) -> ProcessState {
+        self.as_mut().ui(context, ui, state)
+    }
+
+    fn on_enter(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.as_mut().on_enter(context, state)
+    }
+
+    fn on_exit(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.as_mut().on_exit(context, state)
+    }
+}
@@ -1,56 +1,277 @@
+use std::{cell::Cell, rc::Rc};
+
 use macroquad::{
-    prelude::{mouse_position, vec2, Color, Vec2},
-    shapes::draw_rectangle,
-    text::draw_text,
-    time::get_frame_time,
-    window::clear_background,
+    prelude::{Color, Vec2},
+    texture::{DrawTextureParams, Texture2D},
+};
+
+use super::{
+    backend::{Backend, MacroquadBackend},
+    input::InputSnapshot,
 };
 
+/// How much time has passed, as seen by the current frame
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameTime {
+    /// the time in seconds since the previous frame
+    pub delta: f32,
+    /// the total time in seconds that has passed, including this frame
+    pub elapsed: f64,
+    /// the number of the current frame, starting at 1
+    pub frame: u64,
+}
+
+impl FrameTime {
+    pub fn new(delta: f32, elapsed: f64, frame: u64) -> Self {
+        Self {
+            delta,
+            elapsed,
+            frame,
+        }
+    }
+    /// The time of the frame that comes after this one, if it took `delta` seconds
+    pub fn next(&self, delta: f32) -> Self {
+        Self {
+            delta,
+            elapsed: self.elapsed + f64::from(delta),
+            frame: self.frame + 1,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Context {
     viewport_size: Vec2,
+    backend: Rc<dyn Backend>,
+    window_size: Option<Vec2>,
+    time: FrameTime,
+    input: Rc<InputSnapshot>,
+    interpolation_alpha: f32,
+    exit_requested: Rc<Cell<bool>>,
+    offset: Vec2,
+    opacity: f32,
 }
 
 impl Context {
+    /// Creates a context that draws to the macroquad window.
+    ///
+    /// The time starts out at [FrameTime::default], so the delta is 0 and components like timers and tweens don't advance
+    /// until a time is set with [Context::with_time]. [StateFull](crate::components::logic::StateFull) does this every frame
     pub fn new(viewport_size: Vec2) -> Self {
-        Self { viewport_size }
+        Self::new_with_backend(viewport_size, Rc::new(MacroquadBackend::new()))
+    }
+    /// creates a context that sends every draw call to the given backend, with the same default time as [Context::new]
+    pub fn new_with_backend(viewport_size: Vec2, backend: Rc<dyn Backend>) -> Self {
+        Self {
+            viewport_size,
+            backend,
+            window_size: None,
+            time: FrameTime::default(),
+            input: Rc::new(InputSnapshot::new()),
+            interpolation_alpha: 1.,
+            exit_requested: Rc::new(Cell::new(false)),
+            offset: Vec2::ZERO,
+            opacity: 1.,
+        }
+    }
+    /// sets the time that components will see
+    pub fn with_time(mut self, time: FrameTime) -> Self {
+        self.time = time;
+        self
+    }
+    /// Sets the window size that components will see, instead of the size of the window the backend draws to.
+    ///
+    /// Used when replaying a recording, so the mouse ends up at the same location as when it got recorded
+    pub fn with_window_size(mut self, window_size: Vec2) -> Self {
+        self.window_size = Some(window_size);
+        self
+    }
+    /// sets the input that components will see
+    pub fn with_input(mut self, input: Rc<InputSnapshot>) -> Self {
+        self.input = input;
+        self
+    }
+    /// sets how far the game is between the last and the next fixed update
+    pub fn with_interpolation_alpha(mut self, interpolation_alpha: f32) -> Self {
+        self.interpolation_alpha = interpolation_alpha;
+        self
+    }
+    /// moves everything drawn with this context by the given offset, on top of any offset it already had
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset += offset;
+        self
+    }
+    /// Removes the offset of this context, for things that are positioned relative to something that already got moved.
+    ///
+    /// Like the content of a ui [Window](crate::components::ui::Window), which is positioned relative to the window
+    pub fn without_offset(mut self) -> Self {
+        self.offset = Vec2::ZERO;
+        self
+    }
+    /// makes everything drawn with this context more transparent, 1 leaves it as is and 0 makes it invisible
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity *= opacity;
+        self
+    }
+    /// creates a copy of this context with a different viewport size
+    pub fn with_viewport_size(&self, viewport_size: Vec2) -> Self {
+        Self {
+            viewport_size,
+            ..self.clone()
+        }
+    }
+    pub fn backend(&self) -> &dyn Backend {
+        self.backend.as_ref()
     }
     pub fn viewport_size(&self) -> Vec2 {
         self.viewport_size
     }
     pub fn window_size(&self) -> Vec2 {
-        vec2(
-            macroquad::window::screen_width(),
-            macroquad::window::screen_height(),
-        )
+        self.window_size
+            .unwrap_or_else(|| self.backend.window_size())
     }
     pub fn get_scale(&self) -> f32 {
         let viewport = self.viewport_size();
         let window = self.window_size();
         f32::min(window.x / viewport.x, window.y / viewport.y)
     }
+    pub fn input(&self) -> &InputSnapshot {
+        &self.input
+    }
+    /// The location of the mouse in the same coordinates things get drawn with by this context.
+    ///
+    /// This takes the scaling and centering of the viewport into account, as well as the offset of the context
     pub fn get_mouse_location(&self) -> Vec2 {
-        let mouse_pos = mouse_position();
+        let mouse_pos = self.input.mouse_position;
         let scale = self.get_scale();
         let window = self.window_size();
         let viewport = self.viewport_size();
         Vec2 {
-            x: (mouse_pos.0 - (window.x - (viewport.x * scale))) / scale,
-            y: (mouse_pos.1 - (window.y - (viewport.y * scale))) / scale,
+            x: (mouse_pos.x - (window.x - (viewport.x * scale)) * 0.5) / scale - self.offset.x,
+            y: (mouse_pos.y - (window.y - (viewport.y * scale)) * 0.5) / scale - self.offset.y,
         }
     }
+    /// How far everything drawn with this context gets moved
+    pub fn offset(&self) -> Vec2 {
+        self.offset
+    }
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+    fn apply_opacity(&self, mut color: Color) -> Color {
+        color.a *= self.opacity;
+        color
+    }
+    /// Clears the screen with the given color.
+    ///
+    /// If the context got moved or made transparent then only the area of the viewport gets covered instead,
+    /// so whatever else is on the screen stays visible
     pub fn clear_background(&self, color: Color) {
-        clear_background(color)
+        if self.offset == Vec2::ZERO && self.opacity >= 1. {
+            self.backend.clear_background(color)
+        } else {
+            let size = self.viewport_size();
+            self.draw_rectangle(0., 0., size.x, size.y, color)
+        }
     }
     pub fn draw_rectangle(&self, x: f32, y: f32, w: f32, h: f32, color: Color) {
-        draw_rectangle(x, y, w, h, color)
+        self.backend.draw_rectangle(
+            x + self.offset.x,
+            y + self.offset.y,
+            w,
+            h,
+            self.apply_opacity(color),
+        )
     }
     pub fn draw_text(&self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
-        draw_text(text, x, y, font_size, color)
+        self.backend.draw_text(
+            text,
+            x + self.offset.x,
+            y + self.offset.y,
+            font_size,
+            self.apply_opacity(color),
+        )
+    }
+    /// Draws the texture like [macroquad::texture::draw_texture_ex] does, the pivot gets moved together with the texture
+    pub fn draw_texture(
+        &self,
+        texture: &Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        params: &DrawTextureParams,
+    ) {
+        let params = DrawTextureParams {
+            pivot: params.pivot.map(|v| v + self.offset),
+            ..params.clone()
+        };
+        self.backend.draw_texture(
+            texture,
+            x + self.offset.x,
+            y + self.offset.y,
+            self.apply_opacity(color),
+            &params,
+        )
+    }
+    /// Draws a layer of a tiled map into `dest`, see [Backend::draw_tiles].
+    ///
+    /// Tiles are drawn with their own colors, so the opacity of the context doesn't apply to them
+    #[cfg(feature = "macroquad-tiled")]
+    pub fn draw_tiles(
+        &self,
+        map: &macroquad_tiled::Map,
+        layer: &str,
+        dest: macroquad::prelude::Rect,
+        source: Option<macroquad::prelude::Rect>,
+    ) {
+        self.backend.draw_tiles(
+            map,
+            layer,
+            macroquad::prelude::Rect {
+                x: dest.x + self.offset.x,
+                y: dest.y + self.offset.y,
+                ..dest
+            },
+            source,
+        )
+    }
+    pub fn time(&self) -> FrameTime {
+        self.time
     }
     pub fn get_delta(&self) -> f32 {
-        get_frame_time()
+        self.time.delta
+    }
+    pub fn get_elapsed(&self) -> f64 {
+        self.time.elapsed
+    }
+    pub fn get_frame(&self) -> u64 {
+        self.time.frame
+    }
+    /// How far along the game is between the last fixed update and the next one, between 0 and 1.
+    ///
+    /// Rendering can use this to smooth out movement when [LoopMode::Fixed](crate::components::logic::LoopMode::Fixed) is used.
+    /// Outside of a fixed loop this is always 1
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha
+    }
+    /// Asks whatever runs the game loop to stop after the current frame.
+    ///
+    /// Every context made from this one, like the ones [Viewport](crate::components::render::Viewport) makes, shares the request.
+    pub fn request_exit(&self) {
+        self.exit_requested.set(true)
+    }
+    pub fn is_exit_requested(&self) -> bool {
+        self.exit_requested.get()
     }
     pub fn set_default_material(&self) {
-        macroquad::material::gl_use_default_material()
+        self.backend.set_default_material()
+    }
+    /// Everything drawn until [Context::end_viewport] ends up in a separate target of the given size
+    pub fn begin_viewport(&self, size: Vec2) {
+        self.backend.begin_viewport(size)
+    }
+    /// Draws the target started by [Context::begin_viewport], scaled to fit
+    pub fn end_viewport(&self) {
+        self.backend.end_viewport()
     }
 }
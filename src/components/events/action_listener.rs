@@ -0,0 +1,234 @@
+use macroquad::prelude::Vec2;
+
+use crate::components::{
+    input::{ActionState, InputMap},
+    Component, Context,
+};
+
+/// Fires off an event every process with the current state of the given action, as bound in the [InputMap] that `get_map` points to
+pub struct ActionListener<GetMap, OnEvent> {
+    action: String,
+    get_map: GetMap,
+    on_event: OnEvent,
+}
+impl<GetMap, OnEvent> ActionListener<GetMap, OnEvent> {
+    pub fn new<State>(action: impl Into<String>, get_map: GetMap, on_event: OnEvent) -> Self
+    where
+        GetMap: Fn(&State) -> &InputMap,
+        OnEvent: Fn(ActionState, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((action.into(), get_map, on_event))
+    }
+}
+
+impl<State, GetMap: Fn(&State) -> &InputMap, OnEvent: Fn(ActionState, &mut State)>
+    Component<&State, &mut State> for ActionListener<GetMap, OnEvent>
+{
+    type Input = (String, GetMap, OnEvent);
+
+    fn instantiate((action, get_map, on_event): Self::Input) -> Self {
+        Self {
+            action,
+            get_map,
+            on_event,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let res = (self.get_map)(state).action_state(context.input(), &self.action);
+        (self.on_event)(res, state);
+        state
+    }
+}
+
+/// Fires off an event every process with the current value of the given axis, as bound in the [InputMap] that `get_map` points to
+pub struct AxisListener<GetMap, OnEvent> {
+    axis: String,
+    get_map: GetMap,
+    on_event: OnEvent,
+}
+impl<GetMap, OnEvent> AxisListener<GetMap, OnEvent> {
+    pub fn new<State>(axis: impl Into<String>, get_map: GetMap, on_event: OnEvent) -> Self
+    where
+        GetMap: Fn(&State) -> &InputMap,
+        OnEvent: Fn(f32, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((axis.into(), get_map, on_event))
+    }
+}
+
+impl<State, GetMap: Fn(&State) -> &InputMap, OnEvent: Fn(f32, &mut State)>
+    Component<&State, &mut State> for AxisListener<GetMap, OnEvent>
+{
+    type Input = (String, GetMap, OnEvent);
+
+    fn instantiate((axis, get_map, on_event): Self::Input) -> Self {
+        Self {
+            axis,
+            get_map,
+            on_event,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let res = (self.get_map)(state).axis(context.input(), &self.axis);
+        (self.on_event)(res, state);
+        state
+    }
+}
+
+/// Combines two axes into a normalized vector and fires it off every process, useful for things like movement
+pub struct Axis2dListener<GetMap, OnEvent> {
+    x_axis: String,
+    y_axis: String,
+    get_map: GetMap,
+    on_event: OnEvent,
+}
+impl<GetMap, OnEvent> Axis2dListener<GetMap, OnEvent> {
+    pub fn new<State>(
+        x_axis: impl Into<String>,
+        y_axis: impl Into<String>,
+        get_map: GetMap,
+        on_event: OnEvent,
+    ) -> Self
+    where
+        GetMap: Fn(&State) -> &InputMap,
+        OnEvent: Fn(Vec2, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((
+            x_axis.into(),
+            y_axis.into(),
+            get_map,
+            on_event,
+        ))
+    }
+}
+
+impl<State, GetMap: Fn(&State) -> &InputMap, OnEvent: Fn(Vec2, &mut State)>
+    Component<&State, &mut State> for Axis2dListener<GetMap, OnEvent>
+{
+    type Input = (String, String, GetMap, OnEvent);
+
+    fn instantiate((x_axis, y_axis, get_map, on_event): Self::Input) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            get_map,
+            on_event,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let res = (self.get_map)(state).axis_2d(context.input(), &self.x_axis, &self.y_axis);
+        (self.on_event)(res, state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::{input::KeyCode, prelude::Vec2};
+
+    use super::{ActionListener, Axis2dListener, AxisListener};
+    use crate::{
+        components::input::{AxisBinding, InputMap},
+        testing::TestHarness,
+    };
+
+    struct State<T> {
+        map: InputMap,
+        seen: Vec<T>,
+    }
+
+    impl<T> State<T> {
+        fn new() -> Self {
+            Self {
+                map: InputMap::new()
+                    .with_action("jump", KeyCode::Space)
+                    .with_axis("x", AxisBinding::new(KeyCode::A, KeyCode::D))
+                    .with_axis("y", AxisBinding::new(KeyCode::W, KeyCode::S)),
+                seen: Vec::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn action_listener_reports_every_frame() {
+        let mut harness = TestHarness::new(
+            State::new(),
+            ActionListener::new(
+                "jump",
+                |v: &State<(bool, bool)>| &v.map,
+                |action, state| state.seen.push((action.down, action.pressed)),
+            ),
+        );
+        harness
+            .frame_with(0.1, |input| {
+                input.press_key(KeyCode::Space);
+            })
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.release_key(KeyCode::Space);
+            });
+        assert_eq!(
+            harness.state().seen,
+            vec![(true, true), (true, false), (false, false)]
+        );
+    }
+
+    #[test]
+    fn action_listener_follows_rebinding() {
+        let mut harness = TestHarness::new(
+            State::new(),
+            ActionListener::new(
+                "jump",
+                |v: &State<bool>| &v.map,
+                |action, state| state.seen.push(action.pressed),
+            ),
+        );
+        harness
+            .state_mut()
+            .map
+            .set_action("jump", vec![KeyCode::Up.into()]);
+        harness
+            .frame_with(0.1, |input| {
+                input.press_key(KeyCode::Space);
+            })
+            .frame_with(0.1, |input| {
+                input.press_key(KeyCode::Up);
+            });
+        assert_eq!(harness.state().seen, vec![false, true]);
+    }
+
+    #[test]
+    fn axis_listeners_report_the_value() {
+        let mut harness = TestHarness::new(
+            State::new(),
+            AxisListener::new(
+                "x",
+                |v: &State<f32>| &v.map,
+                |value, state| state.seen.push(value),
+            ),
+        );
+        harness.frame(0.1).frame_with(0.1, |input| {
+            input.press_key(KeyCode::D);
+        });
+        assert_eq!(harness.state().seen, vec![0., 1.]);
+
+        let mut harness = TestHarness::new(
+            State::new(),
+            Axis2dListener::new(
+                "x",
+                "y",
+                |v: &State<Vec2>| &v.map,
+                |value, state| state.seen.push(value),
+            ),
+        );
+        harness.frame_with(0.1, |input| {
+            input.press_key(KeyCode::A).press_key(KeyCode::W);
+        });
+        let direction = harness.state().seen[0];
+        assert!((direction.length() - 1.).abs() < 1e-6);
+        assert!(direction.x < 0. && direction.y < 0.);
+    }
+}
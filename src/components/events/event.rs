@@ -33,3 +33,38 @@ impl<
         state
     }
 }
+
+/// Works like [Event], except that the check also gets the [Context] so it can look at things like the input or the time
+pub struct ContextEvent<CheckEvent, MapIntoState> {
+    check_event: CheckEvent,
+    map: MapIntoState,
+}
+
+impl<CheckEvent, MapIntoState> ContextEvent<CheckEvent, MapIntoState> {
+    pub fn new<StateIn, CreatedEvent>(check_event: CheckEvent, mapper: MapIntoState) -> Self
+    where
+        CheckEvent: Fn(&Context, &StateIn) -> CreatedEvent,
+        MapIntoState: Fn(CreatedEvent, &mut StateIn),
+    {
+        <Self as Component<&StateIn, &mut StateIn>>::instantiate((check_event, mapper))
+    }
+}
+impl<
+        StateIn,
+        CreatedEvent,
+        CheckEvent: Fn(&Context, &StateIn) -> CreatedEvent,
+        MapIntoState: Fn(CreatedEvent, &mut StateIn),
+    > Component<&StateIn, &mut StateIn> for ContextEvent<CheckEvent, MapIntoState>
+{
+    type Input = (CheckEvent, MapIntoState);
+
+    fn instantiate((check_event, map): Self::Input) -> Self {
+        Self { check_event, map }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut StateIn) -> &'c mut StateIn {
+        let x = (self.check_event)(context, state);
+        (self.map)(x, state);
+        state
+    }
+}
@@ -1,4 +1,4 @@
-use macroquad::{input::KeyCode, prelude::is_key_down};
+use macroquad::input::KeyCode;
 
 use crate::components::{Component, Context};
 
@@ -26,9 +26,184 @@ impl<State, CheckFor: Fn(&State) -> KeyCode, OnEvent: Fn(bool, &mut State)>
         Self { check, on_event }
     }
 
-    fn process<'c>(&mut self, _: &Context, state: &'c mut State) -> &'c mut State {
-        let res = is_key_down((self.check)(state));
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let res = context.input().is_key_down((self.check)(state));
         (self.on_event)(res, state);
         state
     }
 }
+
+/// How often a held key fires again, like holding down a key in a text field
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyRepeat {
+    /// how long in seconds the key needs to be held before it starts repeating
+    pub delay: f32,
+    /// the time in seconds between two repeats
+    pub interval: f32,
+}
+
+impl KeyRepeat {
+    pub fn new(delay: f32, interval: f32) -> Self {
+        Self { delay, interval }
+    }
+    /// how often the key repeated when it has been held for the given amount of time
+    fn repeats_after(&self, held_for: f32) -> u32 {
+        if held_for < self.delay || self.interval <= 0. {
+            0
+        } else {
+            ((held_for - self.delay) / self.interval) as u32 + 1
+        }
+    }
+}
+
+/// This component fires off an event every process to tell if the given key went down during this frame.
+///
+/// With [KeyPressed::with_repeat] it also tells `true` while the key is held, once for every repeat
+pub struct KeyPressed<CheckFor, OnEvent> {
+    check: CheckFor,
+    on_event: OnEvent,
+    repeat: Option<KeyRepeat>,
+    held_for: Option<f32>,
+}
+impl<CheckFor, OnEvent> KeyPressed<CheckFor, OnEvent> {
+    pub fn new<State>(check: CheckFor, on_event: OnEvent) -> Self
+    where
+        CheckFor: Fn(&State) -> KeyCode,
+        OnEvent: Fn(bool, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((check, on_event))
+    }
+    /// keeps firing while the key is held down
+    pub fn with_repeat(mut self, repeat: KeyRepeat) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+}
+
+impl<State, CheckFor: Fn(&State) -> KeyCode, OnEvent: Fn(bool, &mut State)>
+    Component<&State, &mut State> for KeyPressed<CheckFor, OnEvent>
+{
+    type Input = (CheckFor, OnEvent);
+
+    fn instantiate((check, on_event): Self::Input) -> Self {
+        Self {
+            check,
+            on_event,
+            repeat: None,
+            held_for: None,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let key = (self.check)(state);
+        let input = context.input();
+        let mut fired = 0;
+        if input.is_key_pressed(key) {
+            self.held_for = Some(0.);
+            fired = 1;
+        } else if input.is_key_down(key) {
+            if let (Some(repeat), Some(held_for)) = (self.repeat, self.held_for) {
+                let now_held_for = held_for + context.get_delta();
+                fired = repeat.repeats_after(now_held_for) - repeat.repeats_after(held_for);
+                self.held_for = Some(now_held_for);
+            }
+        } else {
+            self.held_for = None;
+        }
+        if fired == 0 {
+            (self.on_event)(false, state);
+        }
+        for _ in 0..fired {
+            (self.on_event)(true, state);
+        }
+        state
+    }
+}
+
+/// This component fires off an event every process to tell if the given key went up during this frame
+pub struct KeyReleased<CheckFor, OnEvent> {
+    check: CheckFor,
+    on_event: OnEvent,
+}
+impl<CheckFor, OnEvent> KeyReleased<CheckFor, OnEvent> {
+    pub fn new<State>(check: CheckFor, on_event: OnEvent) -> Self
+    where
+        CheckFor: Fn(&State) -> KeyCode,
+        OnEvent: Fn(bool, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((check, on_event))
+    }
+}
+
+impl<State, CheckFor: Fn(&State) -> KeyCode, OnEvent: Fn(bool, &mut State)>
+    Component<&State, &mut State> for KeyReleased<CheckFor, OnEvent>
+{
+    type Input = (CheckFor, OnEvent);
+
+    fn instantiate((check, on_event): Self::Input) -> Self {
+        Self { check, on_event }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let res = context.input().is_key_released((self.check)(state));
+        (self.on_event)(res, state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::input::KeyCode;
+
+    use super::{KeyPressed, KeyReleased, KeyRepeat};
+    use crate::testing::TestHarness;
+
+    fn enter(_: &Vec<bool>) -> KeyCode {
+        KeyCode::Enter
+    }
+
+    fn push(fired: bool, state: &mut Vec<bool>) {
+        state.push(fired)
+    }
+
+    #[test]
+    fn key_pressed_tells_every_frame_if_the_key_went_down() {
+        let mut harness = TestHarness::new(Vec::new(), KeyPressed::new(enter, push));
+        harness
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.press_key(KeyCode::Enter);
+            })
+            .frame(0.1);
+        assert_eq!(harness.state(), &vec![false, true, false]);
+    }
+
+    #[test]
+    fn key_pressed_repeats_while_held() {
+        let mut harness = TestHarness::new(
+            Vec::new(),
+            KeyPressed::new(enter, push).with_repeat(KeyRepeat::new(0.5, 0.25)),
+        );
+        harness
+            .frame_with(0.25, |input| {
+                input.press_key(KeyCode::Enter);
+            })
+            .frame(0.25)
+            .frame(0.5);
+        assert_eq!(harness.state(), &vec![true, false, true, true]);
+    }
+
+    #[test]
+    fn key_released_tells_every_frame_if_the_key_went_up() {
+        let mut harness = TestHarness::new(Vec::new(), KeyReleased::new(enter, push));
+        harness
+            .frame_with(0.1, |input| {
+                input.press_key(KeyCode::Enter);
+            })
+            .frame_with(0.1, |input| {
+                input.release_key(KeyCode::Enter);
+            })
+            .frame(0.1);
+        assert_eq!(harness.state(), &vec![false, true, false]);
+    }
+}
@@ -1,7 +1,15 @@
+mod action_listener;
 mod event;
 mod keyboard_listener;
+mod mouse_listener;
+mod pointer;
+mod queue;
 mod timer;
 
-pub use event::Event;
-pub use keyboard_listener::KeyDown;
-pub use timer::{Timer, VariableTimer};
+pub use action_listener::{ActionListener, Axis2dListener, AxisListener};
+pub use event::{ContextEvent, Event};
+pub use keyboard_listener::{KeyDown, KeyPressed, KeyReleased, KeyRepeat};
+pub use mouse_listener::{MouseClicked, MouseHeld, MouseWheel};
+pub use pointer::{PointerArea, PointerEvent, PointerState};
+pub use queue::{EventEmitter, EventListener, EventQueue};
+pub use timer::{StateTimer, Timer, TimerMode, TimerState, VariableTimer};
@@ -0,0 +1,223 @@
+use macroquad::{input::MouseButton, prelude::Vec2};
+
+use crate::components::{Component, Context};
+
+/// This component calls `on_event` with the location of the mouse during the frame the given button went back up after being pressed.
+///
+/// Only presses this component saw count, so a button that was already held down when it started processing doesn't click.
+/// The location is in the same coordinates things get drawn with, see [Context::get_mouse_location]
+pub struct MouseClicked<CheckFor, OnEvent> {
+    check: CheckFor,
+    on_event: OnEvent,
+    pressed: bool,
+}
+impl<CheckFor, OnEvent> MouseClicked<CheckFor, OnEvent> {
+    pub fn new<State>(check: CheckFor, on_event: OnEvent) -> Self
+    where
+        CheckFor: Fn(&State) -> MouseButton,
+        OnEvent: Fn(Vec2, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((check, on_event))
+    }
+}
+
+impl<State, CheckFor: Fn(&State) -> MouseButton, OnEvent: Fn(Vec2, &mut State)>
+    Component<&State, &mut State> for MouseClicked<CheckFor, OnEvent>
+{
+    type Input = (CheckFor, OnEvent);
+
+    fn instantiate((check, on_event): Self::Input) -> Self {
+        Self {
+            check,
+            on_event,
+            pressed: false,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let button = (self.check)(state);
+        let input = context.input();
+        if input.is_mouse_button_pressed(button) {
+            self.pressed = true;
+        }
+        if self.pressed && input.is_mouse_button_released(button) {
+            self.pressed = false;
+            (self.on_event)(context.get_mouse_location(), state);
+        }
+        state
+    }
+    fn on_exit<'c>(&mut self, _: &Context, state: &'c mut State) -> &'c mut State {
+        self.pressed = false;
+        state
+    }
+}
+
+/// This component listens to the given mouse button and fires off an event every process to tell if it is held down
+pub struct MouseHeld<CheckFor, OnEvent> {
+    check: CheckFor,
+    on_event: OnEvent,
+}
+impl<CheckFor, OnEvent> MouseHeld<CheckFor, OnEvent> {
+    pub fn new<State>(check: CheckFor, on_event: OnEvent) -> Self
+    where
+        CheckFor: Fn(&State) -> MouseButton,
+        OnEvent: Fn(bool, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((check, on_event))
+    }
+}
+
+impl<State, CheckFor: Fn(&State) -> MouseButton, OnEvent: Fn(bool, &mut State)>
+    Component<&State, &mut State> for MouseHeld<CheckFor, OnEvent>
+{
+    type Input = (CheckFor, OnEvent);
+
+    fn instantiate((check, on_event): Self::Input) -> Self {
+        Self { check, on_event }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let res = context.input().is_mouse_button_down((self.check)(state));
+        (self.on_event)(res, state);
+        state
+    }
+}
+
+/// This component calls `on_event` with how far the mouse wheel moved, but only during frames where it moved
+pub struct MouseWheel<OnEvent> {
+    on_event: OnEvent,
+}
+impl<OnEvent> MouseWheel<OnEvent> {
+    pub fn new<State>(on_event: OnEvent) -> Self
+    where
+        OnEvent: Fn(Vec2, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate(on_event)
+    }
+}
+
+impl<State, OnEvent: Fn(Vec2, &mut State)> Component<&State, &mut State> for MouseWheel<OnEvent> {
+    type Input = OnEvent;
+
+    fn instantiate(on_event: Self::Input) -> Self {
+        Self { on_event }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let wheel = context.input().mouse_wheel;
+        if wheel != Vec2::ZERO {
+            (self.on_event)(wheel, state);
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::{
+        input::MouseButton,
+        prelude::{vec2, Vec2},
+    };
+
+    use super::{MouseClicked, MouseHeld, MouseWheel};
+    use crate::testing::TestHarness;
+
+    fn left(_: &Vec<Vec2>) -> MouseButton {
+        MouseButton::Left
+    }
+
+    fn push(location: Vec2, state: &mut Vec<Vec2>) {
+        state.push(location)
+    }
+
+    #[test]
+    fn clicked_fires_once_the_button_is_released() {
+        let mut harness = TestHarness::new(Vec::new(), MouseClicked::new(left, push));
+        harness.frame_with(0.1, |input| {
+            input.press_mouse_button(MouseButton::Left);
+        });
+        assert!(harness.state().is_empty());
+        harness
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.release_mouse_button(MouseButton::Left);
+            })
+            .frame(0.1);
+        assert_eq!(harness.state().len(), 1);
+    }
+
+    #[test]
+    fn clicked_ignores_a_release_without_a_press() {
+        let mut harness = TestHarness::new(Vec::new(), MouseClicked::new(left, push));
+        harness.frame_with(0.1, |input| {
+            input.release_mouse_button(MouseButton::Left);
+        });
+        assert!(harness.state().is_empty());
+        // a button that was already down when the listener started doesn't click either
+        let mut harness = TestHarness::new(Vec::new(), MouseClicked::new(left, push));
+        harness
+            .frame_with(0.1, |input| {
+                input.mouse_buttons_down.insert(MouseButton::Left);
+            })
+            .frame_with(0.1, |input| {
+                input.release_mouse_button(MouseButton::Left);
+            });
+        assert!(harness.state().is_empty());
+    }
+
+    #[test]
+    fn clicked_ignores_other_buttons() {
+        let mut harness = TestHarness::new(Vec::new(), MouseClicked::new(left, push));
+        harness
+            .frame_with(0.1, |input| {
+                input.press_mouse_button(MouseButton::Right);
+            })
+            .frame_with(0.1, |input| {
+                input.release_mouse_button(MouseButton::Right);
+            });
+        assert!(harness.state().is_empty());
+    }
+
+    #[test]
+    fn clicked_fires_at_the_mouse_location() {
+        let mut harness = TestHarness::new(Vec::new(), MouseClicked::new(left, push));
+        harness.frame_with(0.1, |input| {
+            input
+                .press_mouse_button(MouseButton::Left)
+                .release_mouse_button(MouseButton::Left)
+                .move_mouse(vec2(10., 20.));
+        });
+        assert_eq!(harness.state(), &[vec2(10., 20.)]);
+    }
+
+    #[test]
+    fn held_reports_every_frame() {
+        let mut harness = TestHarness::new(
+            Vec::new(),
+            MouseHeld::new(
+                |_: &Vec<bool>| MouseButton::Left,
+                |held, state: &mut Vec<bool>| state.push(held),
+            ),
+        );
+        harness
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.press_mouse_button(MouseButton::Left);
+            })
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.release_mouse_button(MouseButton::Left);
+            });
+        assert_eq!(harness.state(), &[false, true, true, false]);
+    }
+
+    #[test]
+    fn wheel_only_fires_when_it_moved() {
+        let mut harness = TestHarness::new(Vec::new(), MouseWheel::new(push));
+        harness
+            .frame(0.1)
+            .frame_with(0.1, |input| input.mouse_wheel = vec2(0., -1.))
+            .frame(0.1);
+        assert_eq!(harness.state(), &[vec2(0., -1.)]);
+    }
+}
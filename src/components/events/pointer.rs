@@ -0,0 +1,397 @@
+use macroquad::{
+    input::MouseButton,
+    prelude::{Rect, Vec2},
+};
+
+use crate::components::{Component, Context};
+
+/// Something the mouse did to a [PointerArea]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointerEvent {
+    /// the mouse started hovering over the area
+    HoverEnter,
+    /// the mouse stopped hovering over the area
+    HoverLeave,
+    /// the left mouse button went down while hovering over the area
+    Press(Vec2),
+    /// the left mouse button went up over the area it went down on, without dragging
+    Click(Vec2),
+    /// the mouse moved far enough while held down on the area. Contains where the press started
+    DragStart(Vec2),
+    /// the mouse moved while dragging, even if it isn't over the area anymore
+    DragMove { position: Vec2, delta: Vec2 },
+    /// the left mouse button went up while dragging
+    DragEnd(Vec2),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Press {
+    id: u64,
+    start: Vec2,
+    last: Vec2,
+    dragging: bool,
+}
+
+/// Keeps track of which [PointerArea] is on top of the mouse, meant to live in the state.
+///
+/// Every area registers itself while being processed, areas that get processed later are drawn later and so count as being on top.
+/// Which area is on top only gets known once every area got processed, so the areas see it one frame late.
+#[derive(Clone, Debug, Default)]
+pub struct PointerState {
+    frame: u64,
+    registered: Vec<(u64, bool)>,
+    hovered: Option<u64>,
+    previously_hovered: Option<u64>,
+    pressed: Option<Press>,
+}
+
+impl PointerState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// the id of the area the mouse is on top of
+    pub fn hovered(&self) -> Option<u64> {
+        self.hovered
+    }
+    /// the id of the area the mouse button went down on, as long as it is held down
+    pub fn pressed(&self) -> Option<u64> {
+        self.pressed.map(|v| v.id)
+    }
+    /// true while an area is being dragged
+    pub fn is_dragging(&self) -> bool {
+        self.pressed.is_some_and(|v| v.dragging)
+    }
+    /// moves the state to the given frame, working out which area ended up on top during the last one
+    pub fn update(&mut self, frame: u64) {
+        if frame == self.frame {
+            return;
+        }
+        let registered = std::mem::take(&mut self.registered);
+        self.previously_hovered = self.hovered;
+        self.hovered = registered
+            .iter()
+            .rev()
+            .find(|(_, contains_mouse)| *contains_mouse)
+            .map(|(id, _)| *id);
+        if let Some(press) = self.pressed {
+            if !registered.iter().any(|(id, _)| *id == press.id) {
+                self.pressed = None;
+            }
+        }
+        self.frame = frame;
+    }
+    fn register(&mut self, id: u64, contains_mouse: bool) {
+        self.registered.push((id, contains_mouse))
+    }
+}
+
+/// Reports what the mouse does to the area that `get_area` returns, in the same coordinates things get drawn with.
+///
+/// The `id` needs to be unique among all areas that share the same [PointerState], [macroquad::hash] is an easy way to get one.
+/// Only the left mouse button is used.
+pub struct PointerArea<GetPointer, GetArea, OnEvent> {
+    id: u64,
+    get_pointer: GetPointer,
+    get_area: GetArea,
+    on_event: OnEvent,
+    drag_threshold: f32,
+}
+
+impl<GetPointer, GetArea, OnEvent> PointerArea<GetPointer, GetArea, OnEvent> {
+    pub fn new<State>(
+        id: u64,
+        get_pointer: GetPointer,
+        get_area: GetArea,
+        on_event: OnEvent,
+    ) -> Self
+    where
+        GetPointer: Fn(&mut State) -> &mut PointerState,
+        GetArea: Fn(&State) -> Rect,
+        OnEvent: Fn(PointerEvent, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((id, get_pointer, get_area, on_event))
+    }
+    /// how far the mouse needs to move while held down before it counts as dragging. Defaults to 4
+    pub fn with_drag_threshold(mut self, drag_threshold: f32) -> Self {
+        self.drag_threshold = drag_threshold;
+        self
+    }
+}
+
+impl<
+        State,
+        GetPointer: Fn(&mut State) -> &mut PointerState,
+        GetArea: Fn(&State) -> Rect,
+        OnEvent: Fn(PointerEvent, &mut State),
+    > Component<&State, &mut State> for PointerArea<GetPointer, GetArea, OnEvent>
+{
+    type Input = (u64, GetPointer, GetArea, OnEvent);
+
+    fn instantiate((id, get_pointer, get_area, on_event): Self::Input) -> Self {
+        Self {
+            id,
+            get_pointer,
+            get_area,
+            on_event,
+            drag_threshold: 4.,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let area = (self.get_area)(state);
+        let location = context.get_mouse_location();
+        let input = context.input();
+        let pointer = (self.get_pointer)(state);
+        pointer.update(context.get_frame());
+        pointer.register(self.id, area.contains(location));
+
+        let is_hovered = pointer.hovered == Some(self.id);
+        let was_hovered = pointer.previously_hovered == Some(self.id);
+        let mut events = Vec::new();
+        if is_hovered && !was_hovered {
+            events.push(PointerEvent::HoverEnter)
+        } else if was_hovered && !is_hovered {
+            events.push(PointerEvent::HoverLeave)
+        }
+        match &mut pointer.pressed {
+            Some(press) if press.id == self.id => {
+                if !input.is_mouse_button_down(MouseButton::Left) {
+                    if press.dragging {
+                        events.push(PointerEvent::DragEnd(location))
+                    } else if is_hovered {
+                        events.push(PointerEvent::Click(location))
+                    }
+                    pointer.pressed = None;
+                } else {
+                    if !press.dragging && press.start.distance(location) > self.drag_threshold {
+                        press.dragging = true;
+                        events.push(PointerEvent::DragStart(press.start));
+                    }
+                    if press.dragging && press.last != location {
+                        events.push(PointerEvent::DragMove {
+                            position: location,
+                            delta: location - press.last,
+                        });
+                    }
+                    press.last = location;
+                }
+            }
+            None if is_hovered && input.is_mouse_button_pressed(MouseButton::Left) => {
+                pointer.pressed = Some(Press {
+                    id: self.id,
+                    start: location,
+                    last: location,
+                    dragging: false,
+                });
+                events.push(PointerEvent::Press(location))
+            }
+            _ => {}
+        }
+        for event in events {
+            (self.on_event)(event, state);
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::{
+        input::MouseButton,
+        prelude::{vec2, Rect},
+    };
+
+    use super::{PointerArea, PointerEvent, PointerState};
+    use crate::{testing::TestHarness, Component};
+
+    #[derive(Default)]
+    struct State {
+        pointer: PointerState,
+        events: Vec<(u64, PointerEvent)>,
+    }
+
+    fn pointer(state: &mut State) -> &mut PointerState {
+        &mut state.pointer
+    }
+
+    fn bottom(_: &State) -> Rect {
+        Rect::new(0., 0., 100., 100.)
+    }
+
+    fn top(_: &State) -> Rect {
+        Rect::new(50., 50., 100., 100.)
+    }
+
+    fn record_bottom(event: PointerEvent, state: &mut State) {
+        state.events.push((1, event))
+    }
+
+    fn record_top(event: PointerEvent, state: &mut State) {
+        state.events.push((2, event))
+    }
+
+    /// two overlapping areas, the one with id 2 gets processed last and so is on top
+    fn areas() -> impl for<'a> Component<&'a State, &'a mut State> {
+        (
+            PointerArea::new(1, pointer, bottom, record_bottom),
+            PointerArea::new(2, pointer, top, record_top),
+        )
+    }
+
+    fn take_events(
+        harness: &mut TestHarness<State, impl for<'a> Component<&'a State, &'a mut State>>,
+    ) -> Vec<(u64, PointerEvent)> {
+        std::mem::take(&mut harness.state_mut().events)
+    }
+
+    #[test]
+    fn hovering_moves_between_areas_one_frame_late() {
+        let mut harness = TestHarness::new(State::default(), areas());
+        harness.frame_with(0.1, |input| {
+            input.move_mouse(vec2(75., 75.));
+        });
+        assert_eq!(take_events(&mut harness), vec![]);
+        harness.frame(0.1);
+        assert_eq!(
+            take_events(&mut harness),
+            vec![(2, PointerEvent::HoverEnter)]
+        );
+        assert_eq!(harness.state().pointer.hovered(), Some(2));
+
+        harness.frame_with(0.1, |input| {
+            input.move_mouse(vec2(10., 10.));
+        });
+        harness.frame(0.1);
+        assert_eq!(
+            take_events(&mut harness),
+            vec![(1, PointerEvent::HoverEnter), (2, PointerEvent::HoverLeave)]
+        );
+        harness.frame_with(0.1, |input| {
+            input.move_mouse(vec2(500., 500.));
+        });
+        harness.frame(0.1);
+        assert_eq!(
+            take_events(&mut harness),
+            vec![(1, PointerEvent::HoverLeave)]
+        );
+        assert_eq!(harness.state().pointer.hovered(), None);
+    }
+
+    #[test]
+    fn only_the_top_area_gets_clicked() {
+        let mut harness = TestHarness::new(State::default(), areas());
+        harness
+            .frame_with(0.1, |input| {
+                input.move_mouse(vec2(75., 75.));
+            })
+            .frame(0.1);
+        take_events(&mut harness);
+        harness.frame_with(0.1, |input| {
+            input.press_mouse_button(MouseButton::Left);
+        });
+        assert_eq!(
+            take_events(&mut harness),
+            vec![(2, PointerEvent::Press(vec2(75., 75.)))]
+        );
+        assert_eq!(harness.state().pointer.pressed(), Some(2));
+        harness.frame_with(0.1, |input| {
+            input.release_mouse_button(MouseButton::Left);
+        });
+        assert_eq!(
+            take_events(&mut harness),
+            vec![(2, PointerEvent::Click(vec2(75., 75.)))]
+        );
+        assert_eq!(harness.state().pointer.pressed(), None);
+    }
+
+    #[test]
+    fn releasing_outside_the_area_is_not_a_click() {
+        let mut harness = TestHarness::new(State::default(), areas());
+        harness
+            .frame_with(0.1, |input| {
+                input.move_mouse(vec2(75., 75.));
+            })
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.press_mouse_button(MouseButton::Left);
+            });
+        take_events(&mut harness);
+        // jumping away is further than the drag threshold, so this becomes a drag instead
+        harness
+            .frame_with(0.1, |input| {
+                input.move_mouse(vec2(500., 500.));
+            })
+            .frame_with(0.1, |input| {
+                input.release_mouse_button(MouseButton::Left);
+            });
+        let events = take_events(&mut harness);
+        assert!(!events
+            .iter()
+            .any(|(_, event)| matches!(event, PointerEvent::Click(_))));
+    }
+
+    #[test]
+    fn dragging_follows_the_mouse_outside_the_area() {
+        let mut harness = TestHarness::new(State::default(), areas());
+        harness
+            .frame_with(0.1, |input| {
+                input.move_mouse(vec2(75., 75.));
+            })
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.press_mouse_button(MouseButton::Left);
+            });
+        take_events(&mut harness);
+
+        harness.frame_with(0.1, |input| {
+            input.move_mouse(vec2(77., 75.));
+        });
+        assert_eq!(take_events(&mut harness), vec![]);
+        assert!(!harness.state().pointer.is_dragging());
+
+        harness.frame_with(0.1, |input| {
+            input.move_mouse(vec2(90., 75.));
+        });
+        assert_eq!(
+            take_events(&mut harness),
+            vec![
+                (2, PointerEvent::DragStart(vec2(75., 75.))),
+                (
+                    2,
+                    PointerEvent::DragMove {
+                        position: vec2(90., 75.),
+                        delta: vec2(13., 0.)
+                    }
+                ),
+            ]
+        );
+        assert!(harness.state().pointer.is_dragging());
+
+        harness.frame_with(0.1, |input| {
+            input.move_mouse(vec2(300., 300.));
+        });
+        assert_eq!(
+            take_events(&mut harness),
+            vec![(
+                2,
+                PointerEvent::DragMove {
+                    position: vec2(300., 300.),
+                    delta: vec2(210., 225.)
+                }
+            )]
+        );
+
+        harness.frame_with(0.1, |input| {
+            input.release_mouse_button(MouseButton::Left);
+        });
+        let events = take_events(&mut harness);
+        assert_eq!(
+            events,
+            vec![
+                (2, PointerEvent::HoverLeave),
+                (2, PointerEvent::DragEnd(vec2(300., 300.)))
+            ]
+        );
+        assert!(!harness.state().pointer.is_dragging());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::components::{Component, Context};
+
+/// A queue of events that can live in the state.
+///
+/// Events sent during frame N can be read during frame N+1, after which they are cleared.
+/// This way every component gets to see every event exactly once, no matter where it is in the scene tree.
+#[derive(Clone, Debug)]
+pub struct EventQueue<T> {
+    frame: u64,
+    readable: Vec<T>,
+    sent: Vec<T>,
+    /// true while an [EventListener] took the readable events out to hand them to its callback
+    lent: bool,
+}
+
+impl<T> EventQueue<T> {
+    pub fn new() -> Self {
+        Self {
+            frame: 0,
+            readable: Vec::new(),
+            sent: Vec::new(),
+            lent: false,
+        }
+    }
+    /// sends an event, which can be read during the next frame
+    pub fn send(&mut self, context: &Context, event: T) {
+        self.update(context.get_frame());
+        self.sent.push(event)
+    }
+    /// the events that were sent during the previous frame
+    pub fn read(&mut self, context: &Context) -> &[T] {
+        self.update(context.get_frame());
+        &self.readable
+    }
+    /// the events that were readable the last time the queue got used, without moving the queue to the current frame.
+    ///
+    /// Meant for render, where the queue can't be changed
+    pub fn peek(&self) -> &[T] {
+        &self.readable
+    }
+    /// moves the queue to the given frame, clearing everything that is too old to be read
+    pub fn update(&mut self, frame: u64) {
+        if frame == self.frame {
+            return;
+        }
+        if frame == self.frame + 1 {
+            self.readable = std::mem::take(&mut self.sent);
+        } else {
+            self.readable.clear();
+            self.sent.clear();
+        }
+        self.frame = frame;
+    }
+}
+
+impl<T> Default for EventQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sends the event that `check_event` returns into the queue that `get_queue` points to, if it returned one
+pub struct EventEmitter<GetQueue, CheckEvent> {
+    get_queue: GetQueue,
+    check_event: CheckEvent,
+}
+
+impl<GetQueue, CheckEvent> EventEmitter<GetQueue, CheckEvent> {
+    pub fn new<State, T>(get_queue: GetQueue, check_event: CheckEvent) -> Self
+    where
+        GetQueue: Fn(&mut State) -> &mut EventQueue<T>,
+        CheckEvent: Fn(&Context, &State) -> Option<T>,
+    {
+        <Self as Component<&State, &mut State>>::instantiate((get_queue, check_event))
+    }
+}
+
+impl<
+        State,
+        T,
+        GetQueue: Fn(&mut State) -> &mut EventQueue<T>,
+        CheckEvent: Fn(&Context, &State) -> Option<T>,
+    > Component<&State, &mut State> for EventEmitter<GetQueue, CheckEvent>
+{
+    type Input = (GetQueue, CheckEvent);
+
+    fn instantiate((get_queue, check_event): Self::Input) -> Self {
+        Self {
+            get_queue,
+            check_event,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        if let Some(event) = (self.check_event)(context, state) {
+            (self.get_queue)(state).send(context, event);
+        }
+        state
+    }
+}
+
+/// Calls `on_event` for every event that can be read from the queue that `get_queue` points to
+pub struct EventListener<GetQueue, OnEvent> {
+    get_queue: GetQueue,
+    on_event: OnEvent,
+}
+
+impl<GetQueue, OnEvent> EventListener<GetQueue, OnEvent> {
+    pub fn new<State, T>(get_queue: GetQueue, on_event: OnEvent) -> Self
+    where
+        GetQueue: Fn(&mut State) -> &mut EventQueue<T>,
+        OnEvent: Fn(&T, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((get_queue, on_event))
+    }
+}
+
+impl<State, T, GetQueue: Fn(&mut State) -> &mut EventQueue<T>, OnEvent: Fn(&T, &mut State)>
+    Component<&State, &mut State> for EventListener<GetQueue, OnEvent>
+{
+    type Input = (GetQueue, OnEvent);
+
+    fn instantiate((get_queue, on_event): Self::Input) -> Self {
+        Self {
+            get_queue,
+            on_event,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let queue = (self.get_queue)(state);
+        queue.update(context.get_frame());
+        // taken out of the queue so on_event can change the state, events it sends go to the next frame
+        let events = std::mem::take(&mut queue.readable);
+        queue.lent = true;
+        for event in &events {
+            (self.on_event)(event, state);
+        }
+        // on_event may have replaced the queue, like when a restart resets the whole state. The old events don't belong in the new one
+        let queue = (self.get_queue)(state);
+        if queue.lent {
+            queue.readable = events;
+            queue.lent = false;
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use macroquad::prelude::vec2;
+
+    use super::{EventEmitter, EventListener, EventQueue};
+    use crate::{
+        components::{backend::RecordingBackend, Context, FrameTime},
+        testing::TestHarness,
+    };
+
+    fn context(frame: u64) -> Context {
+        Context::new_with_backend(
+            vec2(100., 100.),
+            Rc::new(RecordingBackend::new(vec2(100., 100.))),
+        )
+        .with_time(FrameTime::new(0.1, frame as f64 * 0.1, frame))
+    }
+
+    #[test]
+    fn events_can_be_read_during_the_next_frame_only() {
+        let mut queue = EventQueue::new();
+        queue.send(&context(1), 1);
+        assert_eq!(queue.read(&context(1)), &[] as &[i32]);
+        assert_eq!(queue.read(&context(2)), &[1]);
+        // every reader in the same frame sees the same events
+        assert_eq!(queue.read(&context(2)), &[1]);
+        assert_eq!(queue.peek(), &[1]);
+        assert_eq!(queue.read(&context(3)), &[] as &[i32]);
+    }
+
+    #[test]
+    fn skipped_frames_clear_the_queue() {
+        let mut queue = EventQueue::new();
+        queue.send(&context(1), 1);
+        queue.send(&context(1), 2);
+        assert_eq!(queue.read(&context(3)), &[] as &[i32]);
+        queue.send(&context(3), 3);
+        queue.update(5);
+        assert_eq!(queue.peek(), &[] as &[i32]);
+    }
+
+    #[test]
+    fn peek_does_not_move_to_the_next_frame() {
+        let mut queue = EventQueue::new();
+        queue.send(&context(1), 1);
+        assert_eq!(queue.peek(), &[] as &[i32]);
+        queue.update(2);
+        assert_eq!(queue.peek(), &[1]);
+    }
+
+    #[derive(Default)]
+    struct State {
+        queue: EventQueue<u32>,
+        send: Option<u32>,
+        seen: Vec<u32>,
+    }
+
+    fn queue(state: &mut State) -> &mut EventQueue<u32> {
+        &mut state.queue
+    }
+
+    fn to_send(_: &Context, state: &State) -> Option<u32> {
+        state.send
+    }
+
+    fn see(event: &u32, state: &mut State) {
+        state.seen.push(*event)
+    }
+
+    #[test]
+    fn listeners_see_what_was_emitted_the_frame_before() {
+        let mut harness = TestHarness::new(
+            State::default(),
+            (
+                EventEmitter::new(queue, to_send),
+                EventListener::new(queue, see),
+            ),
+        );
+        harness.state_mut().send = Some(1);
+        harness.frame(0.1);
+        assert!(harness.state().seen.is_empty());
+        harness.state_mut().send = None;
+        harness.frame(0.1);
+        assert_eq!(harness.state().seen, [1]);
+        harness.frame(0.1);
+        assert_eq!(harness.state().seen, [1]);
+        // the events stay readable for components after the listener
+        harness.state_mut().send = Some(2);
+        harness.frame(0.1).frame(0.1);
+        assert_eq!(harness.state().queue.peek(), &[2]);
+    }
+
+    fn restart(_: &u32, state: &mut State) {
+        *state = State::default();
+    }
+
+    #[test]
+    fn events_do_not_end_up_in_a_queue_that_replaced_the_old_one() {
+        let mut harness = TestHarness::new(
+            State::default(),
+            (
+                EventEmitter::new(queue, to_send),
+                EventListener::new(queue, restart),
+            ),
+        );
+        harness.state_mut().send = Some(1);
+        harness.frame(0.1).frame(0.1);
+        assert_eq!(harness.state().queue.peek(), &[] as &[u32]);
+        assert_eq!(harness.state().send, None);
+        harness.frame(0.1);
+        assert_eq!(harness.state().queue.peek(), &[] as &[u32]);
+    }
+}
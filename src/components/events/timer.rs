@@ -1,4 +1,4 @@
-use macroquad::time::get_frame_time;
+use serde::{Deserialize, Serialize};
 
 use crate::components::{Component, Context};
 
@@ -33,8 +33,8 @@ impl<RenderState: Clone, ProcessState, OnReachedTime: Fn(&mut ProcessState)>
         Self::new(max_time, on_reached_time)
     }
 
-    fn process(&mut self, _: &Context, mut state: ProcessState) -> ProcessState {
-        let frame_time = get_frame_time();
+    fn process(&mut self, ctx: &Context, mut state: ProcessState) -> ProcessState {
+        let frame_time = ctx.get_delta();
         self.current_time += frame_time;
         while self.current_time > self.max_time {
             (self.on_reached_time)(&mut state);
@@ -88,3 +88,273 @@ impl<X: Clone, State, GetMaxTime: Fn(&State) -> f32, OnReachedTime: Fn(&Context,
         state
     }
 }
+
+/// How often a [TimerState] fires before it is finished
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimerMode {
+    Once,
+    Repeat(u32),
+    Forever,
+}
+
+/// The progress of a timer, meant to live in the state so it gets saved and reset together with everything else.
+///
+/// Use [StateTimer] to run it from the scene tree, or [TimerState::tick] to run it by hand
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimerState {
+    duration: f32,
+    elapsed: f32,
+    mode: TimerMode,
+    times_fired: u32,
+    paused: bool,
+}
+
+impl TimerState {
+    pub fn new(duration: f32, mode: TimerMode) -> Self {
+        Self {
+            duration,
+            elapsed: 0.,
+            mode,
+            times_fired: 0,
+            paused: false,
+        }
+    }
+    /// a timer that fires a single time after `duration` seconds
+    pub fn once(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Once)
+    }
+    /// a timer that fires every `duration` seconds, `times` times in total
+    pub fn repeat(duration: f32, times: u32) -> Self {
+        Self::new(duration, TimerMode::Repeat(times))
+    }
+    /// a timer that fires every `duration` seconds and never finishes
+    pub fn forever(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Forever)
+    }
+    /// creates the timer in a paused state
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+    /// changes how long the timer takes, the time that already passed is kept
+    pub fn set_duration(&mut self, duration: f32) {
+        self.duration = duration
+    }
+    /// the time in seconds since the timer last fired, or since it started
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+    /// how far the timer is until it fires next, between 0 and 1
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0. {
+            1.
+        } else {
+            (self.elapsed / self.duration).clamp(0., 1.)
+        }
+    }
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+    pub fn times_fired(&self) -> u32 {
+        self.times_fired
+    }
+    /// true once the timer fired as often as its mode allows
+    pub fn is_finished(&self) -> bool {
+        match self.mode {
+            TimerMode::Once => self.times_fired >= 1,
+            TimerMode::Repeat(times) => self.times_fired >= times,
+            TimerMode::Forever => false,
+        }
+    }
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    pub fn pause(&mut self) {
+        self.paused = true
+    }
+    pub fn resume(&mut self) {
+        self.paused = false
+    }
+    /// starts the timer over, as if it was just created. Whether it is paused stays the same
+    pub fn reset(&mut self) {
+        self.elapsed = 0.;
+        self.times_fired = 0;
+    }
+    /// moves the timer forward by `delta` seconds and returns how often it fired
+    pub fn tick(&mut self, delta: f32) -> u32 {
+        self.advance(delta);
+        let mut fired = 0;
+        while self.fire() {
+            fired += 1;
+        }
+        fired
+    }
+    fn advance(&mut self, delta: f32) {
+        if !self.paused && !self.is_finished() {
+            self.elapsed += delta
+        }
+    }
+    /// fires the timer once if enough time has passed.
+    ///
+    /// A timer without a duration fires once per call to [TimerState::advance], instead of endlessly
+    fn fire(&mut self) -> bool {
+        if self.is_finished() || self.elapsed < self.duration {
+            return false;
+        }
+        if self.duration <= 0. {
+            if self.elapsed <= 0. {
+                return false;
+            }
+            self.elapsed = 0.;
+        } else {
+            self.elapsed -= self.duration;
+        }
+        self.times_fired += 1;
+        if self.is_finished() {
+            self.elapsed = self.duration.max(0.);
+        }
+        true
+    }
+}
+
+/// Runs the [TimerState] that `get_timer` points to and calls `on_reached_time` every time it fires.
+///
+/// Unlike [Timer] and [VariableTimer] the progress lives in the state, so resetting the state resets the timer as well
+pub struct StateTimer<GetTimer, OnReachedTime> {
+    get_timer: GetTimer,
+    on_reached_time: OnReachedTime,
+}
+
+impl<GetTimer, OnReachedTime> StateTimer<GetTimer, OnReachedTime> {
+    pub fn new<State>(get_timer: GetTimer, on_reached_time: OnReachedTime) -> Self
+    where
+        GetTimer: Fn(&mut State) -> &mut TimerState,
+        OnReachedTime: Fn(&Context, &mut State),
+    {
+        <Self as Component<&State, &mut State>>::instantiate((get_timer, on_reached_time))
+    }
+}
+
+impl<
+        X: Clone,
+        State,
+        GetTimer: Fn(&mut State) -> &mut TimerState,
+        OnReachedTime: Fn(&Context, &mut State),
+    > Component<X, &mut State> for StateTimer<GetTimer, OnReachedTime>
+{
+    type Input = (GetTimer, OnReachedTime);
+
+    fn instantiate((get_timer, on_reached_time): Self::Input) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            get_timer,
+            on_reached_time,
+        }
+    }
+
+    fn process<'c>(&mut self, ctx: &Context, state: &'c mut State) -> &'c mut State {
+        (self.get_timer)(state).advance(ctx.get_delta());
+        // the timer gets looked up again after every call, so `on_reached_time` can change its duration or pause it
+        while (self.get_timer)(state).fire() {
+            (self.on_reached_time)(ctx, state);
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StateTimer, Timer, TimerState, VariableTimer};
+    use crate::testing::TestHarness;
+
+    fn count(v: &mut &mut u32) {
+        **v += 1
+    }
+
+    #[test]
+    fn timer_fires_every_time_the_time_passed() {
+        let mut harness = TestHarness::new(0u32, Timer::new(0.5, count));
+        harness.frame(0.3);
+        assert_eq!(*harness.state(), 0);
+        harness.frame(0.3);
+        assert_eq!(*harness.state(), 1);
+        harness.frames(5, 0.3);
+        assert_eq!(*harness.state(), 4);
+    }
+
+    #[test]
+    fn timer_catches_up_after_a_long_frame() {
+        let mut harness = TestHarness::new(0u32, Timer::new(0.5, count));
+        harness.frame(1.6);
+        assert_eq!(*harness.state(), 3);
+    }
+
+    #[test]
+    fn variable_timer_reads_the_time_from_the_state() {
+        let mut harness = TestHarness::new(
+            (0.5f32, 0u32),
+            VariableTimer::new(|v: &(f32, u32)| v.0, |_, v: &mut (f32, u32)| v.1 += 1),
+        );
+        harness.frame(0.6);
+        assert_eq!(harness.state().1, 1);
+        harness.state_mut().0 = 2.;
+        harness.frame(0.6);
+        assert_eq!(harness.state().1, 1);
+    }
+
+    #[test]
+    fn timer_state_fires_once() {
+        let mut timer = TimerState::once(1.);
+        assert_eq!(timer.tick(0.5), 0);
+        assert_eq!(timer.progress(), 0.5);
+        assert_eq!(timer.tick(0.6), 1);
+        assert!(timer.is_finished());
+        assert_eq!(timer.tick(5.), 0);
+        assert_eq!(timer.times_fired(), 1);
+    }
+
+    #[test]
+    fn timer_state_repeats_the_given_amount_of_times() {
+        let mut timer = TimerState::repeat(1., 3);
+        assert_eq!(timer.tick(2.5), 2);
+        assert!(!timer.is_finished());
+        assert_eq!(timer.tick(2.5), 1);
+        assert!(timer.is_finished());
+        assert_eq!(timer.times_fired(), 3);
+    }
+
+    #[test]
+    fn timer_state_does_not_advance_while_paused() {
+        let mut timer = TimerState::forever(1.).paused();
+        assert_eq!(timer.tick(3.), 0);
+        timer.resume();
+        assert_eq!(timer.tick(3.), 3);
+        timer.reset();
+        assert_eq!(timer.times_fired(), 0);
+        assert_eq!(timer.elapsed(), 0.);
+    }
+
+    #[test]
+    fn state_timer_keeps_its_progress_in_the_state() {
+        let mut harness = TestHarness::new(
+            (TimerState::repeat(0.5, 2), 0u32),
+            StateTimer::new(
+                |v: &mut (TimerState, u32)| &mut v.0,
+                |_, v: &mut (TimerState, u32)| v.1 += 1,
+            ),
+        );
+        harness.frames(3, 0.3);
+        assert_eq!(harness.state().1, 1);
+        harness.frames(10, 0.3);
+        assert_eq!(harness.state().1, 2);
+        assert!(harness.state().0.is_finished());
+        harness.state_mut().0.reset();
+        harness.frames(2, 0.3);
+        assert_eq!(harness.state().1, 3);
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use macroquad::{
+    input::{KeyCode, MouseButton},
+    prelude::{vec2, Vec2},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{serialize, InputSnapshot, KEY_CODES, MOUSE_BUTTONS};
+
+/// A single key or mouse button
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(#[serde(with = "serialize::key_code")] KeyCode),
+    MouseButton(#[serde(with = "serialize::mouse_button")] MouseButton),
+}
+
+impl InputBinding {
+    pub fn is_down(&self, input: &InputSnapshot) -> bool {
+        match self {
+            InputBinding::Key(key) => input.is_key_down(*key),
+            InputBinding::MouseButton(button) => input.is_mouse_button_down(*button),
+        }
+    }
+    /// true only during the frame the key or button went down
+    pub fn is_pressed(&self, input: &InputSnapshot) -> bool {
+        match self {
+            InputBinding::Key(key) => input.is_key_pressed(*key),
+            InputBinding::MouseButton(button) => input.is_mouse_button_pressed(*button),
+        }
+    }
+    /// true only during the frame the key or button went up
+    pub fn is_released(&self, input: &InputSnapshot) -> bool {
+        match self {
+            InputBinding::Key(key) => input.is_key_released(*key),
+            InputBinding::MouseButton(button) => input.is_mouse_button_released(*button),
+        }
+    }
+    /// The first key or mouse button that got pressed this frame, if any.
+    ///
+    /// Useful for a settings screen that waits for the player to press whatever they want to bind
+    pub fn first_pressed(input: &InputSnapshot) -> Option<Self> {
+        KEY_CODES
+            .into_iter()
+            .find(|v| input.is_key_pressed(*v))
+            .map(InputBinding::Key)
+            .or_else(|| {
+                MOUSE_BUTTONS
+                    .into_iter()
+                    .find(|v| input.is_mouse_button_pressed(*v))
+                    .map(InputBinding::MouseButton)
+            })
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(value: KeyCode) -> Self {
+        InputBinding::Key(value)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(value: MouseButton) -> Self {
+        InputBinding::MouseButton(value)
+    }
+}
+
+/// A combination of keys and mouse buttons that all need to be held down at the same time, like `LeftControl + S`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Binding {
+    inputs: Vec<InputBinding>,
+}
+
+impl Binding {
+    pub fn new(inputs: impl IntoIterator<Item = InputBinding>) -> Self {
+        Self {
+            inputs: inputs.into_iter().collect(),
+        }
+    }
+    pub fn key(key: KeyCode) -> Self {
+        Self::new([InputBinding::Key(key)])
+    }
+    pub fn mouse_button(button: MouseButton) -> Self {
+        Self::new([InputBinding::MouseButton(button)])
+    }
+    /// a binding that needs all the given keys to be held down
+    pub fn keys(keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        Self::new(keys.into_iter().map(InputBinding::Key))
+    }
+    pub fn inputs(&self) -> &[InputBinding] {
+        &self.inputs
+    }
+    /// true if every key and button is held down. A binding without any keys or buttons is never down
+    pub fn is_down(&self, input: &InputSnapshot) -> bool {
+        !self.inputs.is_empty() && self.inputs.iter().all(|v| v.is_down(input))
+    }
+    /// true only during the frame the last key or button of the combination went down
+    pub fn is_pressed(&self, input: &InputSnapshot) -> bool {
+        self.is_down(input) && self.inputs.iter().any(|v| v.is_pressed(input))
+    }
+    /// true only during the frame the combination stopped being held down
+    pub fn is_released(&self, input: &InputSnapshot) -> bool {
+        self.inputs.iter().any(|v| v.is_released(input))
+            && self
+                .inputs
+                .iter()
+                .all(|v| v.is_down(input) || v.is_released(input))
+    }
+}
+
+impl From<InputBinding> for Binding {
+    fn from(value: InputBinding) -> Self {
+        Self::new([value])
+    }
+}
+
+impl From<KeyCode> for Binding {
+    fn from(value: KeyCode) -> Self {
+        Self::key(value)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    fn from(value: MouseButton) -> Self {
+        Self::mouse_button(value)
+    }
+}
+
+/// The state of an action during a single frame
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActionState {
+    /// true while any of the bindings of the action is held down
+    pub down: bool,
+    /// true only during the frame the action went down, so when a binding went down while no other binding was already held
+    pub pressed: bool,
+    /// true only during the frame the action stopped being held down
+    pub released: bool,
+}
+
+/// The bindings that push an axis towards -1 and towards 1
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub negative: Vec<Binding>,
+    pub positive: Vec<Binding>,
+}
+
+impl AxisBinding {
+    pub fn new(negative: impl Into<Binding>, positive: impl Into<Binding>) -> Self {
+        Self {
+            negative: vec![negative.into()],
+            positive: vec![positive.into()],
+        }
+    }
+    /// -1 if only a negative binding is held down, 1 if only a positive one is, 0 otherwise
+    pub fn value(&self, input: &InputSnapshot) -> f32 {
+        let is_down = |bindings: &[Binding]| bindings.iter().any(|v| v.is_down(input));
+        match (is_down(&self.negative), is_down(&self.positive)) {
+            (true, false) => -1.,
+            (false, true) => 1.,
+            _ => 0.,
+        }
+    }
+}
+
+/// Maps named actions and axes to the keys and mouse buttons that trigger them.
+///
+/// Components ask for the state of an action by its name, so the bindings can change at runtime without touching the scene tree.
+/// The map is serializable, so a settings screen can edit it and save it together with the rest of the settings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputMap {
+    #[serde(default)]
+    actions: BTreeMap<String, Vec<Binding>>,
+    #[serde(default)]
+    axes: BTreeMap<String, AxisBinding>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// adds a binding to the given action
+    pub fn with_action(mut self, action: impl Into<String>, binding: impl Into<Binding>) -> Self {
+        self.bind_action(action, binding);
+        self
+    }
+    /// sets the bindings of the given axis
+    pub fn with_axis(mut self, axis: impl Into<String>, binding: AxisBinding) -> Self {
+        self.set_axis(axis, binding);
+        self
+    }
+    /// adds a binding to the given action, keeping the bindings it already had
+    pub fn bind_action(&mut self, action: impl Into<String>, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        let bindings = self.actions.entry(action.into()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding)
+        }
+    }
+    /// replaces every binding of the given action
+    pub fn set_action(&mut self, action: impl Into<String>, bindings: Vec<Binding>) {
+        self.actions.insert(action.into(), bindings);
+    }
+    /// removes a single binding from the given action
+    pub fn unbind_action(&mut self, action: &str, binding: &Binding) {
+        if let Some(bindings) = self.actions.get_mut(action) {
+            bindings.retain(|v| v != binding)
+        }
+    }
+    pub fn set_axis(&mut self, axis: impl Into<String>, binding: AxisBinding) {
+        self.axes.insert(axis.into(), binding);
+    }
+    /// the bindings of the given action, empty if the action is unknown
+    pub fn action_bindings(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+    pub fn axis_binding(&self, axis: &str) -> Option<&AxisBinding> {
+        self.axes.get(axis)
+    }
+    pub fn axis_binding_mut(&mut self, axis: &str) -> Option<&mut AxisBinding> {
+        self.axes.get_mut(axis)
+    }
+    /// every action together with its bindings, sorted by name
+    pub fn actions(&self) -> impl Iterator<Item = (&str, &[Binding])> {
+        self.actions.iter().map(|(k, v)| (k.as_str(), v.as_slice()))
+    }
+    /// every axis together with its bindings, sorted by name
+    pub fn axes(&self) -> impl Iterator<Item = (&str, &AxisBinding)> {
+        self.axes.iter().map(|(k, v)| (k.as_str(), v))
+    }
+    /// The state of the given action. Unknown actions are never down
+    pub fn action_state(&self, input: &InputSnapshot, action: &str) -> ActionState {
+        let bindings = self.action_bindings(action);
+        let down = bindings.iter().any(|v| v.is_down(input));
+        // a binding that is down without being pressed this frame was already holding the action down
+        let already_down = bindings
+            .iter()
+            .any(|v| v.is_down(input) && !v.is_pressed(input));
+        ActionState {
+            down,
+            pressed: !already_down && bindings.iter().any(|v| v.is_pressed(input)),
+            released: !down && bindings.iter().any(|v| v.is_released(input)),
+        }
+    }
+    pub fn is_action_down(&self, input: &InputSnapshot, action: &str) -> bool {
+        self.action_state(input, action).down
+    }
+    pub fn is_action_pressed(&self, input: &InputSnapshot, action: &str) -> bool {
+        self.action_state(input, action).pressed
+    }
+    pub fn is_action_released(&self, input: &InputSnapshot, action: &str) -> bool {
+        self.action_state(input, action).released
+    }
+    /// The value of the given axis, between -1 and 1. Unknown axes are always 0
+    pub fn axis(&self, input: &InputSnapshot, axis: &str) -> f32 {
+        self.axis_binding(axis)
+            .map(|v| v.value(input))
+            .unwrap_or_default()
+    }
+    /// Combines two axes into a vector, which is normalized so moving diagonally isn't faster
+    pub fn axis_2d(&self, input: &InputSnapshot, x_axis: &str, y_axis: &str) -> Vec2 {
+        vec2(self.axis(input, x_axis), self.axis(input, y_axis)).normalize_or_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::input::KeyCode;
+
+    use super::{ActionState, AxisBinding, Binding, InputMap};
+    use crate::components::input::InputSnapshot;
+
+    fn pause_map() -> InputMap {
+        InputMap::new()
+            .with_action("pause", KeyCode::Space)
+            .with_action("pause", KeyCode::Enter)
+    }
+
+    fn state(down: bool, pressed: bool, released: bool) -> ActionState {
+        ActionState {
+            down,
+            pressed,
+            released,
+        }
+    }
+
+    #[test]
+    fn pressing_a_second_binding_does_not_press_the_action_again() {
+        let map = pause_map();
+        let mut input = InputSnapshot::new();
+        input.press_key(KeyCode::Space);
+        assert_eq!(map.action_state(&input, "pause"), state(true, true, false));
+
+        let mut input = input.next_frame();
+        input.press_key(KeyCode::Enter);
+        assert_eq!(map.action_state(&input, "pause"), state(true, false, false));
+
+        let mut input = input.next_frame();
+        input.release_key(KeyCode::Space);
+        assert_eq!(map.action_state(&input, "pause"), state(true, false, false));
+
+        let mut input = input.next_frame();
+        input.release_key(KeyCode::Enter);
+        assert_eq!(map.action_state(&input, "pause"), state(false, false, true));
+    }
+
+    #[test]
+    fn pressing_two_bindings_at_once_presses_the_action_once() {
+        let map = pause_map();
+        let mut input = InputSnapshot::new();
+        input.press_key(KeyCode::Space).press_key(KeyCode::Enter);
+        assert!(map.is_action_pressed(&input, "pause"));
+        assert!(!map.is_action_pressed(&input.next_frame(), "pause"));
+    }
+
+    #[test]
+    fn combinations_need_every_key() {
+        let map =
+            InputMap::new().with_action("save", Binding::keys([KeyCode::LeftControl, KeyCode::S]));
+        let mut input = InputSnapshot::new();
+        input.press_key(KeyCode::LeftControl);
+        assert!(!map.is_action_down(&input, "save"));
+        let mut input = input.next_frame();
+        input.press_key(KeyCode::S);
+        assert!(map.is_action_pressed(&input, "save"));
+        let mut input = input.next_frame();
+        input.release_key(KeyCode::LeftControl);
+        assert!(map.is_action_released(&input, "save"));
+    }
+
+    #[test]
+    fn rebinding_an_action() {
+        let mut map = pause_map();
+        map.unbind_action("pause", &Binding::key(KeyCode::Space));
+        assert_eq!(
+            map.action_bindings("pause"),
+            &[Binding::key(KeyCode::Enter)]
+        );
+        map.set_action("pause", vec![Binding::key(KeyCode::P)]);
+        let mut input = InputSnapshot::new();
+        input.press_key(KeyCode::P);
+        assert!(map.is_action_pressed(&input, "pause"));
+        assert!(!map.is_action_down(&input, "unknown"));
+    }
+
+    #[test]
+    fn axes() {
+        let map = InputMap::new()
+            .with_axis("x", AxisBinding::new(KeyCode::A, KeyCode::D))
+            .with_axis("y", AxisBinding::new(KeyCode::W, KeyCode::S));
+        let mut input = InputSnapshot::new();
+        input.press_key(KeyCode::A);
+        assert_eq!(map.axis(&input, "x"), -1.);
+        input.press_key(KeyCode::D);
+        assert_eq!(map.axis(&input, "x"), 0.);
+        assert_eq!(map.axis(&input, "unknown"), 0.);
+        let mut input = InputSnapshot::new();
+        input.press_key(KeyCode::D).press_key(KeyCode::S);
+        let direction = map.axis_2d(&input, "x", "y");
+        assert!((direction.length() - 1.).abs() < 1e-6);
+        assert!(direction.x > 0. && direction.y > 0.);
+    }
+}
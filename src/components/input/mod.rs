@@ -0,0 +1,12 @@
+mod bindings;
+mod key_codes;
+mod recording;
+mod serialize;
+mod snapshot;
+
+pub use bindings::{ActionState, AxisBinding, Binding, InputBinding, InputMap};
+pub use key_codes::{key_code_from_name, mouse_button_from_name, KEY_CODES, MOUSE_BUTTONS};
+#[cfg(feature = "serde_json")]
+pub use recording::InputRecorder;
+pub use recording::{InputReplay, RecordedFrame};
+pub use snapshot::InputSnapshot;
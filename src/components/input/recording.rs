@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+#[cfg(feature = "serde_json")]
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use macroquad::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::{serialize, InputSnapshot};
+
+/// Everything that came from outside the scene tree during a single frame
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    pub delta: f32,
+    /// The size of the window during this frame, which the location of the mouse depends on.
+    ///
+    /// When none the window size of the backend gets used
+    #[serde(default, with = "serialize::option_vec2")]
+    pub window_size: Option<Vec2>,
+    pub input: InputSnapshot,
+}
+
+/// Writes every frame it gets to a file, one line of json per frame.
+///
+/// Every frame gets written as soon as it is recorded, so the recording survives the game crashing.
+/// Needs the `serde_json` feature
+#[cfg(feature = "serde_json")]
+pub struct InputRecorder {
+    writer: Box<dyn Write>,
+}
+
+#[cfg(feature = "serde_json")]
+impl InputRecorder {
+    /// creates a recorder that writes to the given file, replacing it if it already exists
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+    /// creates a recorder that writes to the given writer
+    pub fn new(writer: impl Write + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+    pub fn record(&mut self, frame: &RecordedFrame) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.writer, frame)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+/// Hands out the frames of a recording made by [InputRecorder], in the order they were recorded
+pub struct InputReplay {
+    frames: VecDeque<RecordedFrame>,
+}
+
+impl InputReplay {
+    /// loads the recording stored in the given file, needs the `serde_json` feature
+    #[cfg(feature = "serde_json")]
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+    /// loads a recording from the given reader, needs the `serde_json` feature
+    #[cfg(feature = "serde_json")]
+    pub fn from_reader(reader: impl BufRead) -> std::io::Result<Self> {
+        let frames = reader
+            .lines()
+            .filter(|v| !matches!(v, Ok(line) if line.trim().is_empty()))
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect::<std::io::Result<_>>()?;
+        Ok(Self { frames })
+    }
+    pub fn from_frames(frames: Vec<RecordedFrame>) -> Self {
+        Self {
+            frames: frames.into(),
+        }
+    }
+    /// takes the next frame of the recording, if there are any left
+    pub fn next_frame(&mut self) -> Option<RecordedFrame> {
+        self.frames.pop_front()
+    }
+    pub fn is_done(&self) -> bool {
+        self.frames.is_empty()
+    }
+    pub fn frames_left(&self) -> usize {
+        self.frames.len()
+    }
+}
@@ -0,0 +1,159 @@
+//! serde helpers for the macroquad input types, as macroquad doesn't implement serde for them itself
+//!
+//! Keys and mouse buttons are stored by their name, so the files stay readable.
+use std::collections::HashSet;
+
+use macroquad::input::{KeyCode, MouseButton, Touch, TouchPhase};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{key_code_from_name, mouse_button_from_name};
+
+fn sorted_names<T: std::fmt::Debug>(values: impl Iterator<Item = T>) -> Vec<String> {
+    let mut names: Vec<_> = values.map(|v| format!("{v:?}")).collect();
+    names.sort();
+    names
+}
+
+pub(crate) mod key_set {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        keys: &HashSet<KeyCode>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        sorted_names(keys.iter()).serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashSet<KeyCode>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|name| {
+                key_code_from_name(&name)
+                    .ok_or_else(|| D::Error::custom(format!("unknown key {name}")))
+            })
+            .collect()
+    }
+}
+
+pub(crate) mod mouse_button_set {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        buttons: &HashSet<MouseButton>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        sorted_names(buttons.iter()).serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashSet<MouseButton>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|name| {
+                mouse_button_from_name(&name)
+                    .ok_or_else(|| D::Error::custom(format!("unknown mouse button {name}")))
+            })
+            .collect()
+    }
+}
+
+pub(crate) mod vec2 {
+    use macroquad::prelude::{vec2, Vec2};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Vec2, serializer: S) -> Result<S::Ok, S::Error> {
+        [value.x, value.y].serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+        let [x, y] = <[f32; 2]>::deserialize(deserializer)?;
+        Ok(vec2(x, y))
+    }
+}
+
+pub(crate) mod option_vec2 {
+    use macroquad::prelude::{vec2, Vec2};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Vec2>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.map(|v| [v.x, v.y]).serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec2>, D::Error> {
+        let value = Option::<[f32; 2]>::deserialize(deserializer)?;
+        Ok(value.map(|[x, y]| vec2(x, y)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "TouchPhase")]
+enum TouchPhaseDef {
+    Started,
+    Stationary,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Touch")]
+struct TouchDef {
+    id: u64,
+    #[serde(with = "TouchPhaseDef")]
+    phase: TouchPhase,
+    #[serde(with = "vec2")]
+    position: macroquad::prelude::Vec2,
+}
+
+pub(crate) mod touches {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct SerializedTouch(#[serde(with = "TouchDef")] Touch);
+
+    pub fn serialize<S: Serializer>(touches: &[Touch], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(touches.iter().cloned().map(SerializedTouch))
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Touch>, D::Error> {
+        Ok(Vec::<SerializedTouch>::deserialize(deserializer)?
+            .into_iter()
+            .map(|v| v.0)
+            .collect())
+    }
+}
+
+pub(crate) mod key_code {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(key: &KeyCode, serializer: S) -> Result<S::Ok, S::Error> {
+        format!("{key:?}").serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyCode, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        key_code_from_name(&name).ok_or_else(|| D::Error::custom(format!("unknown key {name}")))
+    }
+}
+
+pub(crate) mod mouse_button {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        button: &MouseButton,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        format!("{button:?}").serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<MouseButton, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        mouse_button_from_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("unknown mouse button {name}")))
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use macroquad::{
+    input::{
+        get_char_pressed, is_key_down, is_key_pressed, is_key_released, is_mouse_button_down,
+        is_mouse_button_pressed, is_mouse_button_released, mouse_position, mouse_wheel, touches,
+        KeyCode, MouseButton, Touch,
+    },
+    prelude::{vec2, Vec2},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{serialize, KEY_CODES, MOUSE_BUTTONS};
+
+/// The state of every input device during a single frame.
+///
+/// Components read this through [Context::input](crate::components::Context::input) instead of asking macroquad directly,
+/// so they can just as easily be driven by input made up by a test or a replay.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct InputSnapshot {
+    #[serde(with = "serialize::key_set")]
+    pub keys_down: HashSet<KeyCode>,
+    #[serde(with = "serialize::key_set")]
+    pub keys_pressed: HashSet<KeyCode>,
+    #[serde(with = "serialize::key_set")]
+    pub keys_released: HashSet<KeyCode>,
+    #[serde(with = "serialize::mouse_button_set")]
+    pub mouse_buttons_down: HashSet<MouseButton>,
+    #[serde(with = "serialize::mouse_button_set")]
+    pub mouse_buttons_pressed: HashSet<MouseButton>,
+    #[serde(with = "serialize::mouse_button_set")]
+    pub mouse_buttons_released: HashSet<MouseButton>,
+    /// The location of the mouse in window coordinates
+    #[serde(with = "serialize::vec2")]
+    pub mouse_position: Vec2,
+    #[serde(with = "serialize::vec2")]
+    pub mouse_wheel: Vec2,
+    #[serde(with = "serialize::touches")]
+    pub touches: Vec<Touch>,
+    /// The characters that got typed this frame, in the order they were typed
+    pub chars_typed: Vec<char>,
+}
+
+impl InputSnapshot {
+    /// creates a snapshot where nothing is pressed
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// creates a snapshot with the current state of macroquad's input.
+    ///
+    /// This empties macroquad's queue of typed characters, so should only be called once per frame
+    pub fn from_macroquad() -> Self {
+        let (mouse_x, mouse_y) = mouse_position();
+        let (wheel_x, wheel_y) = mouse_wheel();
+        Self {
+            keys_down: KEY_CODES.into_iter().filter(|v| is_key_down(*v)).collect(),
+            keys_pressed: KEY_CODES
+                .into_iter()
+                .filter(|v| is_key_pressed(*v))
+                .collect(),
+            keys_released: KEY_CODES
+                .into_iter()
+                .filter(|v| is_key_released(*v))
+                .collect(),
+            mouse_buttons_down: MOUSE_BUTTONS
+                .into_iter()
+                .filter(|v| is_mouse_button_down(*v))
+                .collect(),
+            mouse_buttons_pressed: MOUSE_BUTTONS
+                .into_iter()
+                .filter(|v| is_mouse_button_pressed(*v))
+                .collect(),
+            mouse_buttons_released: MOUSE_BUTTONS
+                .into_iter()
+                .filter(|v| is_mouse_button_released(*v))
+                .collect(),
+            mouse_position: vec2(mouse_x, mouse_y),
+            mouse_wheel: vec2(wheel_x, wheel_y),
+            touches: touches(),
+            chars_typed: std::iter::from_fn(get_char_pressed).collect(),
+        }
+    }
+    /// creates the snapshot for the frame after this one, assuming nothing changed.
+    ///
+    /// Keys and buttons stay held down and the mouse stays where it was, everything that only lasts a single frame is cleared
+    pub fn next_frame(&self) -> Self {
+        Self {
+            keys_down: self.keys_down.clone(),
+            mouse_buttons_down: self.mouse_buttons_down.clone(),
+            mouse_position: self.mouse_position,
+            ..Default::default()
+        }
+    }
+    /// adds everything that only lasts a single frame from an earlier snapshot that never got processed
+    pub fn include_earlier(&mut self, earlier: InputSnapshot) {
+        self.keys_pressed.extend(earlier.keys_pressed);
+        self.keys_released.extend(earlier.keys_released);
+        self.mouse_buttons_pressed
+            .extend(earlier.mouse_buttons_pressed);
+        self.mouse_buttons_released
+            .extend(earlier.mouse_buttons_released);
+        self.mouse_wheel += earlier.mouse_wheel;
+        let mut chars_typed = earlier.chars_typed;
+        chars_typed.append(&mut self.chars_typed);
+        self.chars_typed = chars_typed;
+    }
+    pub fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keys_down.contains(&key)
+    }
+    /// true only during the frame the key went down
+    pub fn is_key_pressed(&self, key: KeyCode) -> bool {
+        self.keys_pressed.contains(&key)
+    }
+    /// true only during the frame the key went up
+    pub fn is_key_released(&self, key: KeyCode) -> bool {
+        self.keys_released.contains(&key)
+    }
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_down.contains(&button)
+    }
+    /// true only during the frame the button went down
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_pressed.contains(&button)
+    }
+    /// true only during the frame the button went up
+    pub fn is_mouse_button_released(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_released.contains(&button)
+    }
+    /// marks the key as both pressed and down
+    pub fn press_key(&mut self, key: KeyCode) -> &mut Self {
+        self.keys_down.insert(key);
+        self.keys_pressed.insert(key);
+        self
+    }
+    /// marks the key as released and no longer down
+    pub fn release_key(&mut self, key: KeyCode) -> &mut Self {
+        self.keys_down.remove(&key);
+        self.keys_released.insert(key);
+        self
+    }
+    /// marks the button as both pressed and down
+    pub fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.mouse_buttons_down.insert(button);
+        self.mouse_buttons_pressed.insert(button);
+        self
+    }
+    /// marks the button as released and no longer down
+    pub fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.mouse_buttons_down.remove(&button);
+        self.mouse_buttons_released.insert(button);
+        self
+    }
+    pub fn move_mouse(&mut self, position: Vec2) -> &mut Self {
+        self.mouse_position = position;
+        self
+    }
+    pub fn type_char(&mut self, char: char) -> &mut Self {
+        self.chars_typed.push(char);
+        self
+    }
+}
@@ -1,10 +1,13 @@
 use crate::components::{Component, Context};
 /// Allows you to turn off one of the 2 possible scene tree branches based on the current state
 /// basically an `if condition { true_child } else { false_child }`
+///
+/// When the chosen branch changes, the old branch gets [Component::on_exit] and the new one [Component::on_enter]
 pub struct Choice<Choose, TrueChild, FalseChild> {
     chooser: Choose,
     true_child: TrueChild,
     false_child: FalseChild,
+    active: Option<bool>,
 }
 
 impl<Choose, TrueChild, FalseChild> Choice<Choose, TrueChild, FalseChild> {
@@ -16,6 +19,34 @@ impl<Choose, TrueChild, FalseChild> Choice<Choose, TrueChild, FalseChild> {
     {
         Self::instantiate((choice, true_child, false_child))
     }
+    fn switch_to<State>(&mut self, context: &Context, state: &mut State, choice: Option<bool>)
+    where
+        TrueChild: for<'a> Component<&'a State, &'a mut State>,
+        FalseChild: for<'a> Component<&'a State, &'a mut State>,
+    {
+        if self.active == choice {
+            return;
+        }
+        match self.active {
+            Some(true) => {
+                self.true_child.on_exit(context, state);
+            }
+            Some(false) => {
+                self.false_child.on_exit(context, state);
+            }
+            None => {}
+        }
+        match choice {
+            Some(true) => {
+                self.true_child.on_enter(context, state);
+            }
+            Some(false) => {
+                self.false_child.on_enter(context, state);
+            }
+            None => {}
+        }
+        self.active = choice;
+    }
 }
 
 impl<
@@ -32,11 +63,14 @@ impl<
             chooser,
             true_child,
             false_child,
+            active: None,
         }
     }
 
     fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
-        if (self.chooser)(state) {
+        let choice = (self.chooser)(state);
+        self.switch_to(context, state, Some(choice));
+        if choice {
             self.true_child.process(context, state)
         } else {
             self.false_child.process(context, state)
@@ -56,10 +90,97 @@ impl<
         ui: &mut macroquad::ui::Ui,
         state: &'c mut State,
     ) -> &'c mut State {
-        if (self.chooser)(state) {
+        let choice = (self.chooser)(state);
+        self.switch_to(context, state, Some(choice));
+        if choice {
             self.true_child.ui(context, ui, state)
         } else {
             self.false_child.ui(context, ui, state)
         }
     }
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let choice = (self.chooser)(state);
+        self.switch_to(context, state, Some(choice));
+        state
+    }
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        self.switch_to(context, state, None);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Choice;
+    use crate::{
+        components::{Component, Context},
+        testing::TestHarness,
+    };
+
+    #[derive(Default)]
+    struct Log {
+        use_true: bool,
+        events: Vec<String>,
+    }
+
+    struct Probe(&'static str);
+
+    impl Component<&Log, &mut Log> for Probe {
+        type Input = &'static str;
+
+        fn instantiate(name: Self::Input) -> Self {
+            Self(name)
+        }
+        fn process<'c>(&mut self, _: &Context, state: &'c mut Log) -> &'c mut Log {
+            state.events.push(format!("{} process", self.0));
+            state
+        }
+        fn on_enter<'c>(&mut self, _: &Context, state: &'c mut Log) -> &'c mut Log {
+            state.events.push(format!("{} enter", self.0));
+            state
+        }
+        fn on_exit<'c>(&mut self, _: &Context, state: &'c mut Log) -> &'c mut Log {
+            state.events.push(format!("{} exit", self.0));
+            state
+        }
+    }
+
+    #[test]
+    fn only_processes_the_chosen_branch() {
+        let mut harness = TestHarness::new(
+            Log::default(),
+            Choice::new(|v: &Log| v.use_true, Probe("true"), Probe("false")),
+        );
+        harness.frames(2, 0.1);
+        assert_eq!(
+            harness.state().events,
+            ["false enter", "false process", "false process"]
+        );
+    }
+
+    #[test]
+    fn switching_exits_the_old_branch_and_enters_the_new_one() {
+        let mut harness = TestHarness::new(
+            Log::default(),
+            Choice::new(|v: &Log| v.use_true, Probe("true"), Probe("false")),
+        );
+        harness.frame(0.1);
+        harness.state_mut().use_true = true;
+        harness.frame(0.1);
+        harness.state_mut().use_true = false;
+        harness.frame(0.1);
+        assert_eq!(
+            harness.state().events,
+            [
+                "false enter",
+                "false process",
+                "false exit",
+                "true enter",
+                "true process",
+                "true exit",
+                "false enter",
+                "false process",
+            ]
+        );
+    }
 }
@@ -32,4 +32,12 @@ impl<U: Clone, X, T, Child: for<'a> Component<&'a T, &'a mut T>> Component<U, X>
         self.child.ui(context, ui, &mut self.static_value);
         state
     }
+    fn on_enter(&mut self, context: &Context, state: X) -> X {
+        self.child.on_enter(context, &mut self.static_value);
+        state
+    }
+    fn on_exit(&mut self, context: &Context, state: X) -> X {
+        self.child.on_exit(context, &mut self.static_value);
+        state
+    }
 }
@@ -0,0 +1,335 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::components::{Component, Context};
+
+/// The state [ForEach] works on
+#[derive(Clone, Debug, Default)]
+pub struct ForEachProperties<Item, Shared> {
+    pub items: Vec<Item>,
+    /// Data that every item gets to see and change, like settings or counters
+    pub shared: Shared,
+}
+
+/// The state a child of [ForEach] gets for its item
+#[derive(Clone, Debug)]
+pub struct ItemProperties<Item, Shared> {
+    pub item: Item,
+    pub shared: Shared,
+    /// set this to true to remove the item, its child instance gets dropped with it
+    pub removed: bool,
+}
+
+/// Runs a separate child for every item in a list.
+///
+/// Items are matched to their child by the key that `get_key` returns, so every child keeps its own instance
+/// for as long as its item exists, even when other items get added or removed.
+/// Items without an instance yet get one made by `create_child`, which then gets [Component::on_enter].
+/// Instances whose item is gone or got removed get [Component::on_exit] before being dropped,
+/// if the item is no longer in the list they see it as it was the last time they ran.
+///
+/// Keys should be unique within the list, items with the same key share a single instance.
+pub struct ForEach<GetKey, CreateChild, Key, Item, Child> {
+    get_key: GetKey,
+    create_child: CreateChild,
+    children: HashMap<Key, Instance<Item, Child>>,
+}
+
+struct Instance<Item, Child> {
+    child: Child,
+    /// the item as the child last saw it, so it can still get [Component::on_exit] once the item is gone
+    last_item: Item,
+}
+
+impl<GetKey, CreateChild, Key, Item, Child> ForEach<GetKey, CreateChild, Key, Item, Child> {
+    pub fn new<Shared>(get_key: GetKey, create_child: CreateChild) -> Self
+    where
+        GetKey: Fn(&Item) -> Key,
+        CreateChild: Fn(&Item) -> Child,
+        Key: Hash + Eq + Clone,
+        Child: for<'a> Component<
+            &'a ItemProperties<Item, Shared>,
+            &'a mut ItemProperties<Item, Shared>,
+        >,
+    {
+        Self {
+            get_key,
+            create_child,
+            children: HashMap::new(),
+        }
+    }
+    /// The amount of child instances that currently exist
+    pub fn instance_count(&self) -> usize {
+        self.children.len()
+    }
+    fn update_children<Shared: Clone>(
+        &mut self,
+        context: &Context,
+        state: &mut ForEachProperties<Item, Shared>,
+        mut update: impl FnMut(&mut Child, &mut ItemProperties<Item, Shared>),
+    ) where
+        Item: Clone,
+        GetKey: Fn(&Item) -> Key,
+        CreateChild: Fn(&Item) -> Child,
+        Key: Hash + Eq + Clone,
+        Child: for<'a> Component<
+            &'a ItemProperties<Item, Shared>,
+            &'a mut ItemProperties<Item, Shared>,
+        >,
+    {
+        let items = std::mem::take(&mut state.items);
+        let mut keys = HashSet::with_capacity(items.len());
+        // the shared data is moved from item to item, so it only gets cloned once
+        let mut shared = state.shared.clone();
+        for item in items {
+            let key = (self.get_key)(&item);
+            let mut props = ItemProperties {
+                item,
+                shared,
+                removed: false,
+            };
+            let instance = match self.children.entry(key.clone()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let mut child = (self.create_child)(&props.item);
+                    child.on_enter(context, &mut props);
+                    entry.insert(Instance {
+                        child,
+                        last_item: props.item.clone(),
+                    })
+                }
+            };
+            update(&mut instance.child, &mut props);
+            if props.removed {
+                if let Some(mut instance) = self.children.remove(&key) {
+                    instance.child.on_exit(context, &mut props);
+                }
+            } else {
+                instance.last_item = props.item.clone();
+                keys.insert(key);
+                state.items.push(props.item);
+            }
+            shared = props.shared;
+        }
+        let gone: Vec<_> = self
+            .children
+            .keys()
+            .filter(|key| !keys.contains(key))
+            .cloned()
+            .collect();
+        for key in gone {
+            if let Some(instance) = self.children.remove(&key) {
+                shared = Self::exit(context, instance, shared);
+            }
+        }
+        state.shared = shared;
+    }
+    fn exit<Shared>(
+        context: &Context,
+        mut instance: Instance<Item, Child>,
+        shared: Shared,
+    ) -> Shared
+    where
+        Child: for<'a> Component<
+            &'a ItemProperties<Item, Shared>,
+            &'a mut ItemProperties<Item, Shared>,
+        >,
+    {
+        let mut props = ItemProperties {
+            item: instance.last_item,
+            shared,
+            removed: true,
+        };
+        instance.child.on_exit(context, &mut props);
+        props.shared
+    }
+}
+
+impl<
+        Item: Clone,
+        Shared: Clone,
+        Key: Hash + Eq + Clone,
+        GetKey: Fn(&Item) -> Key,
+        CreateChild: Fn(&Item) -> Child,
+        Child: for<'a> Component<&'a ItemProperties<Item, Shared>, &'a mut ItemProperties<Item, Shared>>,
+    > Component<&ForEachProperties<Item, Shared>, &mut ForEachProperties<Item, Shared>>
+    for ForEach<GetKey, CreateChild, Key, Item, Child>
+{
+    type Input = (GetKey, CreateChild);
+
+    fn instantiate((get_key, create_child): Self::Input) -> Self {
+        Self::new(get_key, create_child)
+    }
+
+    fn process<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut ForEachProperties<Item, Shared>,
+    ) -> &'c mut ForEachProperties<Item, Shared> {
+        self.update_children(context, state, |child, props| {
+            child.process(context, props);
+        });
+        state
+    }
+
+    fn render(&self, context: &Context, props: &ForEachProperties<Item, Shared>) {
+        // render can't change the shared data, so every item can use the same copy of it
+        let mut item_props: Option<ItemProperties<Item, Shared>> = None;
+        for item in &props.items {
+            let item_props = match &mut item_props {
+                Some(item_props) => {
+                    item_props.item = item.clone();
+                    item_props
+                }
+                None => item_props.insert(ItemProperties {
+                    item: item.clone(),
+                    shared: props.shared.clone(),
+                    removed: false,
+                }),
+            };
+            match self.children.get(&(self.get_key)(item)) {
+                Some(instance) => instance.child.render(context, item_props),
+                // the item got added after process ran, so it doesn't have its own instance yet
+                None => (self.create_child)(item).render(context, item_props),
+            }
+        }
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: &'c mut ForEachProperties<Item, Shared>,
+    ) -> &'c mut ForEachProperties<Item, Shared> {
+        self.update_children(context, state, |child, props| {
+            child.ui(context, ui, props);
+        });
+        state
+    }
+
+    fn on_enter<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut ForEachProperties<Item, Shared>,
+    ) -> &'c mut ForEachProperties<Item, Shared> {
+        // every instance gets entered when it is created, and on_exit drops them all
+        self.update_children(context, state, |_, _| {});
+        state
+    }
+
+    fn on_exit<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut ForEachProperties<Item, Shared>,
+    ) -> &'c mut ForEachProperties<Item, Shared> {
+        let mut shared = state.shared.clone();
+        for item in &mut state.items {
+            if let Some(mut instance) = self.children.remove(&(self.get_key)(item)) {
+                let mut props = ItemProperties {
+                    item: item.clone(),
+                    shared,
+                    removed: false,
+                };
+                instance.child.on_exit(context, &mut props);
+                *item = props.item;
+                shared = props.shared;
+            }
+        }
+        for (_, instance) in self.children.drain() {
+            shared = Self::exit(context, instance, shared);
+        }
+        state.shared = shared;
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ForEach, ForEachProperties, ItemProperties};
+    use crate::{
+        components::{Component, Context},
+        testing::TestHarness,
+    };
+
+    /// an item is an id together with whether it wants to be removed
+    type Item = (u32, bool);
+    type Props = ItemProperties<Item, Vec<String>>;
+    type Probes = ForEach<fn(&Item) -> u32, fn(&Item) -> Probe, u32, Item, Probe>;
+
+    struct Probe;
+
+    impl Component<&Props, &mut Props> for Probe {
+        type Input = ();
+
+        fn instantiate(_: Self::Input) -> Self {
+            Self
+        }
+        fn process<'c>(&mut self, _: &Context, state: &'c mut Props) -> &'c mut Props {
+            state.shared.push(format!("{} process", state.item.0));
+            state.removed = state.item.1;
+            state
+        }
+        fn on_enter<'c>(&mut self, _: &Context, state: &'c mut Props) -> &'c mut Props {
+            state.shared.push(format!("{} enter", state.item.0));
+            state
+        }
+        fn on_exit<'c>(&mut self, _: &Context, state: &'c mut Props) -> &'c mut Props {
+            state.shared.push(format!("{} exit", state.item.0));
+            state
+        }
+    }
+
+    fn harness(items: Vec<Item>) -> TestHarness<ForEachProperties<Item, Vec<String>>, Probes> {
+        fn key(item: &Item) -> u32 {
+            item.0
+        }
+        fn create(_: &Item) -> Probe {
+            Probe
+        }
+        TestHarness::new(
+            ForEachProperties {
+                items,
+                shared: Vec::new(),
+            },
+            ForEach::new(key as fn(&_) -> _, create as fn(&_) -> _),
+        )
+    }
+
+    #[test]
+    fn new_instances_get_entered_once() {
+        let mut harness = harness(vec![(1, false)]);
+        harness.frame(0.1);
+        harness.state_mut().items.push((2, false));
+        harness.frame(0.1);
+        assert_eq!(
+            harness.state().shared,
+            ["1 enter", "1 process", "1 process", "2 enter", "2 process"]
+        );
+        assert_eq!(harness.component().instance_count(), 2);
+    }
+
+    #[test]
+    fn removed_items_get_exited() {
+        let mut harness = harness(vec![(1, false), (2, true)]);
+        harness.frame(0.1);
+        assert_eq!(
+            harness.state().shared,
+            ["1 enter", "1 process", "2 enter", "2 process", "2 exit"]
+        );
+        assert_eq!(harness.state().items, [(1, false)]);
+        assert_eq!(harness.component().instance_count(), 1);
+    }
+
+    #[test]
+    fn items_that_are_gone_get_exited() {
+        let mut harness = harness(vec![(1, false), (2, false)]);
+        harness.frame(0.1);
+        harness.state_mut().shared.clear();
+        harness.state_mut().items.retain(|v| v.0 != 1);
+        harness.frame(0.1);
+        assert_eq!(harness.state().shared, ["2 process", "1 exit"]);
+        assert_eq!(harness.component().instance_count(), 1);
+    }
+}
@@ -6,6 +6,7 @@ use super::Selector;
 
 /// Helper to instantiate components if their properties is not perfectly aligned with the current available state
 
+#[allow(clippy::empty_line_after_doc_comments)]
 pub struct Comp<T, C> {
     _state: PhantomData<T>,
     _t: PhantomData<C>,
@@ -17,6 +18,7 @@ impl<State, T: for<'a> Component<&'a State, &'a mut State>> Comp<State, T> {
     }
     /// Configure the function needed to go from State A to the properties needed for Component B
 
+    #[allow(clippy::empty_line_after_doc_comments)]
     pub fn map_in<StateIn, Func: Fn(&StateIn) -> State>(
         func: Func,
     ) -> MapInto<T, StateIn, State, Func> {
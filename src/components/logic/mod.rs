@@ -1,19 +1,23 @@
 mod choice;
 mod contained_state;
+mod for_each;
 mod mapper;
 mod never;
 mod only_render_on;
 mod selector;
 mod statefull;
 mod static_value;
+mod switch;
 mod type_eraser;
 
 pub use choice::Choice;
 pub use contained_state::ContainedState;
+pub use for_each::{ForEach, ForEachProperties, ItemProperties};
 pub use mapper::{Comp, MapInto};
 pub use never::Never;
 pub use only_render_on::{OnlyRenderOn, OnlyRenderWith};
 pub use selector::Selector;
-pub use statefull::StateFull;
+pub use statefull::{LoopMode, StateFull};
 pub use static_value::StaticValue;
+pub use switch::{Branches, Switch};
 pub use type_eraser::Eraser;
@@ -1,8 +1,12 @@
 use crate::{components::Context, Component};
 
+/// Only runs process and ui of its child while `decider` returns true, render always runs.
+///
+/// The child gets [Component::on_enter] once it starts running and [Component::on_exit] once it stops
 pub struct OnlyRenderOn<Child, Decider> {
     child: Child,
     decider: Decider,
+    active: bool,
 }
 impl<Child, Decider> OnlyRenderOn<Child, Decider> {
     pub fn new<State>(child: Child, decider: Decider) -> Self
@@ -10,31 +14,39 @@ impl<Child, Decider> OnlyRenderOn<Child, Decider> {
         Child: for<'a> Component<&'a State, &'a mut State>,
         Decider: Fn(&State) -> bool,
     {
-        Self { child, decider }
+        Self {
+            child,
+            decider,
+            active: false,
+        }
     }
     pub fn process<State>(&mut self, context: &Context, state: &mut State) -> bool
     where
         Child: for<'a> Component<&'a State, &'a mut State>,
         Decider: Fn(&State) -> bool,
     {
-        if self.should_process(state) {
+        let should_process = self.should_process(state);
+        self.set_active(context, state, should_process);
+        if should_process {
             self.child.process(context, state);
             true
         } else {
             false
         }
     }
-    pub fn ui<'a, State>(
+    pub fn ui<State>(
         &mut self,
         context: &Context,
         ui: &mut macroquad::ui::Ui,
-        state: &'a mut State,
+        state: &mut State,
     ) -> bool
     where
-        Child: Component<&'a State, &'a mut State>,
+        Child: for<'a> Component<&'a State, &'a mut State>,
         Decider: Fn(&State) -> bool,
     {
-        if self.should_process(state) {
+        let should_process = self.should_process(state);
+        self.set_active(context, state, should_process);
+        if should_process {
             self.child.ui(context, ui, state);
             true
         } else {
@@ -48,6 +60,20 @@ impl<Child, Decider> OnlyRenderOn<Child, Decider> {
     {
         (self.decider)(state)
     }
+    fn set_active<State>(&mut self, context: &Context, state: &mut State, active: bool)
+    where
+        Child: for<'a> Component<&'a State, &'a mut State>,
+    {
+        if self.active == active {
+            return;
+        }
+        self.active = active;
+        if active {
+            self.child.on_enter(context, state);
+        } else {
+            self.child.on_exit(context, state);
+        }
+    }
 }
 
 impl<State, Child: for<'a> Component<&'a State, &'a mut State>, Decider: Fn(&State) -> bool>
@@ -79,11 +105,22 @@ impl<State, Child: for<'a> Component<&'a State, &'a mut State>, Decider: Fn(&Sta
         self.ui(context, ui, state);
         state
     }
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let should_process = self.should_process(state);
+        self.set_active(context, state, should_process);
+        state
+    }
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        self.set_active(context, state, false);
+        state
+    }
 }
 
+/// Like [OnlyRenderOn], but runs `on_paused_child` while its main child is paused
 pub struct OnlyRenderWith<Child, Decider, OnPausedChild> {
     child: OnlyRenderOn<Child, Decider>,
     on_paused_child: OnPausedChild,
+    paused_active: bool,
 }
 impl<Child, Decider, OnPausedChild> OnlyRenderWith<Child, Decider, OnPausedChild> {
     pub fn new<State>(decider: Decider, child: Child, on_paused_child: OnPausedChild) -> Self
@@ -95,6 +132,21 @@ impl<Child, Decider, OnPausedChild> OnlyRenderWith<Child, Decider, OnPausedChild
         Self {
             child: OnlyRenderOn::new(child, decider),
             on_paused_child,
+            paused_active: false,
+        }
+    }
+    fn set_paused_active<State>(&mut self, context: &Context, state: &mut State, active: bool)
+    where
+        OnPausedChild: for<'a> Component<&'a State, &'a mut State>,
+    {
+        if self.paused_active == active {
+            return;
+        }
+        self.paused_active = active;
+        if active {
+            self.on_paused_child.on_enter(context, state);
+        } else {
+            self.on_paused_child.on_exit(context, state);
         }
     }
 }
@@ -116,7 +168,11 @@ impl<
     }
 
     fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        if self.child.should_process(state) {
+            self.set_paused_active(context, state, false);
+        }
         if !self.child.process(context, state) {
+            self.set_paused_active(context, state, true);
             self.on_paused_child.process(context, state);
         }
         state
@@ -135,10 +191,26 @@ impl<
         ui: &mut macroquad::ui::Ui,
         state: &'c mut State,
     ) -> &'c mut State {
-        self.child.ui(context, ui, state);
-        if !self.child.should_process(state) {
+        if self.child.should_process(state) {
+            self.set_paused_active(context, state, false);
+        }
+        if !self.child.ui(context, ui, state) {
+            self.set_paused_active(context, state, true);
             self.on_paused_child.ui(context, ui, state);
         }
         state
     }
+
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        self.child.on_enter(context, state);
+        let paused = !self.child.should_process(state);
+        self.set_paused_active(context, state, paused);
+        state
+    }
+
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        self.child.on_exit(context, state);
+        self.set_paused_active(context, state, false);
+        state
+    }
 }
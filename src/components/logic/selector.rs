@@ -43,6 +43,18 @@ impl<
         (self.updater)(mapped_state, state);
         state
     }
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut StateIn) -> &'c mut StateIn {
+        let mut mapped_state = (self.mapper)(state);
+        self.child.on_enter(context, &mut mapped_state);
+        (self.updater)(mapped_state, state);
+        state
+    }
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut StateIn) -> &'c mut StateIn {
+        let mut mapped_state = (self.mapper)(state);
+        self.child.on_exit(context, &mut mapped_state);
+        (self.updater)(mapped_state, state);
+        state
+    }
 }
 impl<MapFunc, Updater, Child> Selector<MapFunc, Updater, Child> {
     pub fn new(map: MapFunc, updater: Updater, child: Child) -> Self {
@@ -53,3 +65,96 @@ impl<MapFunc, Updater, Child> Selector<MapFunc, Updater, Child> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Selector;
+    use crate::{
+        components::{events::Event, logic::Choice, Component, Context},
+        testing::TestHarness,
+    };
+
+    #[derive(Debug, Default, PartialEq)]
+    struct State {
+        count: u32,
+        untouched: u32,
+    }
+
+    /// counts enters as ones and exits as tens
+    struct Hooks;
+
+    impl Component<&u32, &mut u32> for Hooks {
+        type Input = ();
+
+        fn instantiate(_: Self::Input) -> Self {
+            Self
+        }
+        fn on_enter<'c>(&mut self, _: &Context, state: &'c mut u32) -> &'c mut u32 {
+            *state += 1;
+            state
+        }
+        fn on_exit<'c>(&mut self, _: &Context, state: &'c mut u32) -> &'c mut u32 {
+            *state += 10;
+            state
+        }
+    }
+
+    #[test]
+    fn maps_the_state_in_and_back_out() {
+        let mut harness = TestHarness::new(
+            State::default(),
+            Selector::new(
+                |v: &State| v.count * 10,
+                |mapped, v: &mut State| v.count = mapped / 10 + 1,
+                Event::new(|_: &u32| (), |_, v: &mut u32| *v += 5),
+            ),
+        );
+        harness.frames(3, 0.1);
+        assert_eq!(
+            harness.state(),
+            &State {
+                count: 3,
+                untouched: 0
+            }
+        );
+    }
+
+    #[test]
+    fn child_sees_the_mapped_state() {
+        let mut harness = TestHarness::new(
+            State {
+                count: 4,
+                untouched: 7,
+            },
+            Selector::new(
+                |v: &State| v.untouched,
+                |seen, v: &mut State| v.count = seen,
+                Event::new(|v: &u32| *v * 2, |doubled, v: &mut u32| *v = doubled),
+            ),
+        );
+        harness.frame(0.1);
+        assert_eq!(harness.state().count, 14);
+        assert_eq!(harness.state().untouched, 7);
+    }
+
+    #[test]
+    fn lifecycle_hooks_see_the_mapped_state() {
+        let mut harness = TestHarness::new(
+            State::default(),
+            Choice::new(
+                |v: &State| v.untouched == 0,
+                Selector::new(
+                    |v: &State| v.count,
+                    |count, v: &mut State| v.count = count,
+                    Hooks,
+                ),
+                Event::new(|_: &State| (), |_, _: &mut State| ()),
+            ),
+        );
+        harness.frame(0.1);
+        assert_eq!(harness.state().count, 1);
+        harness.state_mut().untouched = 1;
+        harness.frame(0.1);
+        assert_eq!(harness.state().count, 11);
+    }
+}
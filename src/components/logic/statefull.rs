@@ -1,43 +1,255 @@
+use std::rc::Rc;
+
+#[cfg(feature = "serde_json")]
+use macroquad::logging::error;
 use macroquad::{
-    prelude::vec2,
-    ui::root_ui,
-    window::{next_frame, screen_height, screen_width},
+    prelude::{vec2, Vec2},
+    time::get_frame_time,
+    window::next_frame,
+};
+
+#[cfg(feature = "serde_json")]
+use crate::components::input::InputRecorder;
+use crate::{
+    components::{
+        backend::Backend,
+        input::{InputReplay, InputSnapshot, RecordedFrame},
+        Context, FrameTime,
+    },
+    Component,
 };
 
-use crate::{components::Context, Component};
+/// How often [StateFull] runs the process step of the scene tree
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoopMode {
+    /// process runs once every frame, with however much time that frame took
+    Variable,
+    /// process runs as often as needed to move time forward in steps of exactly `step` seconds.
+    ///
+    /// Rendering still happens once a frame, [Context::interpolation_alpha] tells how far along the next step is.
+    /// If process would need to run more than `max_steps_per_frame` times in a single frame then the game slows down instead.
+    Fixed { step: f32, max_steps_per_frame: u32 },
+}
+
+impl LoopMode {
+    /// runs process the given amount of times per second
+    pub fn fixed(ticks_per_second: f32) -> Self {
+        LoopMode::Fixed {
+            step: 1. / ticks_per_second,
+            max_steps_per_frame: 8,
+        }
+    }
+}
 
 /// This manages the state and controls the game loop
 pub struct StateFull<T> {
     state: T,
+    context: Context,
+    time: FrameTime,
+    #[cfg(feature = "serde_json")]
+    recorder: Option<InputRecorder>,
+    replay: Option<InputReplay>,
+    loop_mode: LoopMode,
+    accumulator: f32,
+    unprocessed_input: Option<InputSnapshot>,
 }
 impl<T> StateFull<T> {
     /// creates a new instance with the given state
     pub fn new_from(state: T) -> Self {
-        Self { state }
+        Self {
+            state,
+            context: Context::new(vec2(0., 0.)),
+            time: FrameTime::default(),
+            #[cfg(feature = "serde_json")]
+            recorder: None,
+            replay: None,
+            loop_mode: LoopMode::Variable,
+            accumulator: 0.,
+            unprocessed_input: None,
+        }
     }
-    /// starts the game loop
-    pub async fn render<'b, Comp: for<'a> Component<&'a T, &'a mut T>>(
-        &'b mut self,
-        mut component: Comp,
+    /// sets the backend that every draw call gets send to
+    pub fn with_backend(mut self, backend: Rc<dyn Backend>) -> Self {
+        self.context = Context::new_with_backend(self.context.viewport_size(), backend);
+        self
+    }
+    /// records the input, window size and frame time of every frame, so the session can be replayed later.
+    ///
+    /// Needs the `serde_json` feature, which is off by default
+    #[cfg(feature = "serde_json")]
+    pub fn with_recorder(mut self, recorder: InputRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+    /// uses the frames of the given recording instead of the actual input, window size and frame time.
+    ///
+    /// Once the recording runs out the actual input is used again.
+    /// Loading a recording made by `StateFull::with_recorder` needs the `serde_json` feature, see `InputReplay::open`.
+    ///
+    /// Only components that read their input from the [Context] get the replayed input.
+    /// Widgets drawn with macroquad's `root_ui` read the real mouse and keyboard, so clicks on those can't be replayed.
+    pub fn with_replay(mut self, replay: InputReplay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+    /// sets how often process runs compared to render
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Self {
+        self.loop_mode = loop_mode;
+        self
+    }
+    fn create_context(&self, input: Rc<InputSnapshot>, window_size: Option<Vec2>) -> Context {
+        let context = match window_size {
+            Some(window_size) => self.context.clone().with_window_size(window_size),
+            None => self.context.clone(),
+        };
+        context
+            .with_viewport_size(context.window_size())
+            .with_time(self.time)
+            .with_input(input)
+    }
+    /// the input of the next frame, from the replay if there is one and from macroquad otherwise
+    fn next_input(&mut self) -> RecordedFrame {
+        let frame = match self.replay.as_mut().and_then(InputReplay::next_frame) {
+            Some(x) => x,
+            None => RecordedFrame {
+                delta: get_frame_time(),
+                window_size: Some(self.context.window_size()),
+                input: InputSnapshot::from_macroquad(),
+            },
+        };
+        #[cfg(feature = "serde_json")]
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.record(&frame) {
+                error!("Could not record the input of frame {}", self.time.frame);
+                error!("Error: {}", e);
+            }
+        }
+        frame
+    }
+    /// the state as it currently is
+    pub fn state(&self) -> &T {
+        &self.state
+    }
+    pub fn state_mut(&mut self) -> &mut T {
+        &mut self.state
+    }
+    /// stops managing the state and gives it back
+    pub fn into_state(self) -> T {
+        self.state
+    }
+    /// runs a single process, render and ui pass with the given context.
+    ///
+    /// Unlike [StateFull::render] this doesn't wait for the next frame, doesn't read any input and ignores the [LoopMode],
+    /// so the time and input the components see are the ones set on the context.
+    /// This allows the scene tree to be driven from a loop owned by someone else, like a test or another game loop.
+    pub fn step<Comp: for<'a> Component<&'a T, &'a mut T>>(
+        &mut self,
+        component: &mut Comp,
+        context: &Context,
     ) {
-        let mut state = &mut self.state;
-        loop {
-            let context = Context::new(vec2(screen_width(), screen_height()));
-            {
-                state = component.process(&context, state);
+        component.process(context, &mut self.state);
+        self.draw(component, context);
+    }
+    fn draw<Comp: for<'a> Component<&'a T, &'a mut T>>(
+        &mut self,
+        component: &mut Comp,
+        context: &Context,
+    ) {
+        component.render(context, &self.state);
+        context.backend().with_ui(&mut |ui| {
+            component.ui(context, ui, &mut self.state);
+        });
+    }
+    /// runs a single frame of the game loop with the given input, following the [LoopMode]
+    fn run_frame<Comp: for<'a> Component<&'a T, &'a mut T>>(
+        &mut self,
+        component: &mut Comp,
+        frame: RecordedFrame,
+    ) {
+        let context = match self.loop_mode {
+            LoopMode::Variable => {
+                self.time = self.time.next(frame.delta);
+                let context = self.create_context(Rc::new(frame.input), frame.window_size);
+                component.process(&context, &mut self.state);
+                context
             }
-            {
-                component.render(&context, state);
+            LoopMode::Fixed {
+                step,
+                max_steps_per_frame,
+            } => {
+                let mut input = frame.input;
+                if let Some(earlier) = self.unprocessed_input.take() {
+                    input.include_earlier(earlier);
+                }
+                let input = Rc::new(input);
+                self.accumulator += frame.delta;
+                let mut steps = 0;
+                while self.accumulator >= step && steps < max_steps_per_frame {
+                    self.time = self.time.next(step);
+                    // only the first step of a frame gets to see what got pressed this frame
+                    let step_input = if steps == 0 {
+                        input.clone()
+                    } else {
+                        Rc::new(input.next_frame())
+                    };
+                    component.process(
+                        &self.create_context(step_input, frame.window_size),
+                        &mut self.state,
+                    );
+                    self.accumulator -= step;
+                    steps += 1;
+                }
+                if steps == 0 {
+                    self.unprocessed_input = Some(input.as_ref().clone());
+                }
+                if self.accumulator >= step {
+                    // process couldn't keep up, drop the time it is behind rather than trying to catch up later
+                    self.accumulator %= step;
+                }
+                self.create_context(input, frame.window_size)
+                    .with_interpolation_alpha(self.accumulator / step)
             }
+        };
+        self.draw(component, &context);
+    }
+    /// starts the game loop
+    ///
+    /// The loop runs until a component calls [Context::request_exit], after which the final state gets returned
+    pub async fn render<Comp: for<'a> Component<&'a T, &'a mut T>>(self, component: Comp) -> T {
+        let (state, _) = self.render_until(component, |_| None::<()>).await;
+        state
+    }
+    /// starts the game loop, which runs until `should_exit` returns a value or a component calls [Context::request_exit]
+    ///
+    /// `should_exit` gets called at the end of every frame.
+    /// Returns the final state, together with the value returned by `should_exit` if that is what stopped the loop.
+    pub async fn render_until<Comp: for<'a> Component<&'a T, &'a mut T>, Exit>(
+        mut self,
+        mut component: Comp,
+        mut should_exit: impl FnMut(&mut T) -> Option<Exit>,
+    ) -> (T, Option<Exit>) {
+        loop {
+            let frame = self.next_input();
+            self.run_frame(&mut component, frame);
 
-            {
-                let mut ui = root_ui();
-                state = component.ui(&context, &mut ui, state);
+            next_frame().await;
+            if let Some(exit) = self.exit_reason(&mut should_exit) {
+                return (self.state, exit);
             }
-
-            next_frame().await
         }
     }
+    /// checks at the end of a frame whether the game loop should stop.
+    ///
+    /// Gives `Some(None)` when a component called [Context::request_exit]
+    fn exit_reason<Exit>(
+        &mut self,
+        should_exit: &mut impl FnMut(&mut T) -> Option<Exit>,
+    ) -> Option<Option<Exit>> {
+        if let Some(exit) = should_exit(&mut self.state) {
+            return Some(Some(exit));
+        }
+        self.context.is_exit_requested().then_some(None)
+    }
 }
 impl<T: Default> StateFull<T> {
     /// creates a new instance with the default value for the state
@@ -51,3 +263,274 @@ impl<T: Default> Default for StateFull<T> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use macroquad::prelude::{vec2, KeyCode, WHITE};
+
+    use super::{LoopMode, StateFull};
+    use crate::components::{
+        backend::{DrawCall, RecordingBackend},
+        input::{InputSnapshot, RecordedFrame},
+        Component, Context, FrameTime,
+    };
+
+    #[derive(Default)]
+    struct Steps {
+        deltas: Vec<f32>,
+        saw_space: Vec<bool>,
+    }
+
+    /// logs every process and draws a rectangle as wide as the interpolation alpha
+    struct Probe;
+
+    impl Component<&Steps, &mut Steps> for Probe {
+        type Input = ();
+
+        fn instantiate(_: Self::Input) -> Self {
+            Self
+        }
+        fn process<'c>(&mut self, context: &Context, state: &'c mut Steps) -> &'c mut Steps {
+            state.deltas.push(context.get_delta());
+            state
+                .saw_space
+                .push(context.input().is_key_pressed(KeyCode::Space));
+            state
+        }
+        fn render(&self, context: &Context, _: &Steps) {
+            context.draw_rectangle(0., 0., context.interpolation_alpha(), 1., WHITE);
+        }
+    }
+
+    fn state_full(loop_mode: LoopMode) -> (StateFull<Steps>, Rc<RecordingBackend>) {
+        let backend = Rc::new(RecordingBackend::new(vec2(100., 100.)));
+        let state = StateFull::new()
+            .with_backend(backend.clone())
+            .with_loop_mode(loop_mode);
+        (state, backend)
+    }
+
+    fn frame(delta: f32, press_space: bool) -> RecordedFrame {
+        let mut input = InputSnapshot::new();
+        if press_space {
+            input.press_key(KeyCode::Space);
+        }
+        RecordedFrame {
+            delta,
+            window_size: None,
+            input,
+        }
+    }
+
+    fn alpha(backend: &RecordingBackend) -> f32 {
+        match backend.take_draw_calls().as_slice() {
+            [DrawCall::Rectangle { w, .. }] => *w,
+            calls => panic!("expected a single rectangle, got {calls:?}"),
+        }
+    }
+
+    #[test]
+    fn variable_mode_processes_once_a_frame() {
+        let (mut state, backend) = state_full(LoopMode::Variable);
+        state.run_frame(&mut Probe, frame(0.3, false));
+        state.run_frame(&mut Probe, frame(0.01, false));
+        assert_eq!(state.state().deltas, [0.3, 0.01]);
+        assert_eq!(backend.take_draw_calls().len(), 2);
+    }
+
+    #[test]
+    fn fixed_mode_processes_in_whole_steps() {
+        let (mut state, backend) = state_full(LoopMode::Fixed {
+            step: 0.125,
+            max_steps_per_frame: 8,
+        });
+        state.run_frame(&mut Probe, frame(0.3125, false));
+        assert_eq!(state.state().deltas, [0.125, 0.125]);
+        assert_eq!(alpha(&backend), 0.5);
+        state.run_frame(&mut Probe, frame(0.3125, false));
+        assert_eq!(state.state().deltas.len(), 5);
+        assert_eq!(alpha(&backend), 0.);
+    }
+
+    #[test]
+    fn fixed_mode_keeps_input_for_the_next_step() {
+        let (mut state, backend) = state_full(LoopMode::Fixed {
+            step: 0.125,
+            max_steps_per_frame: 8,
+        });
+        state.run_frame(&mut Probe, frame(0.0625, true));
+        assert!(state.state().deltas.is_empty());
+        assert_eq!(alpha(&backend), 0.5);
+        state.run_frame(&mut Probe, frame(0.0625, false));
+        assert_eq!(state.state().saw_space, [true]);
+        // only the first step of a frame sees the press
+        state.run_frame(&mut Probe, frame(0.25, true));
+        assert_eq!(state.state().saw_space, [true, true, false]);
+    }
+
+    #[test]
+    fn fixed_mode_drops_time_it_can_not_keep_up_with() {
+        let (mut state, backend) = state_full(LoopMode::Fixed {
+            step: 0.125,
+            max_steps_per_frame: 2,
+        });
+        state.run_frame(&mut Probe, frame(1., false));
+        assert_eq!(state.state().deltas.len(), 2);
+        assert_eq!(alpha(&backend), 0.);
+        state.run_frame(&mut Probe, frame(0.0625, false));
+        assert_eq!(state.state().deltas.len(), 2);
+        assert_eq!(alpha(&backend), 0.5);
+    }
+
+    /// counts frames and asks to exit once the count reaches its limit
+    struct ExitAt(u32);
+
+    impl Component<&u32, &mut u32> for ExitAt {
+        type Input = u32;
+
+        fn instantiate(limit: Self::Input) -> Self {
+            Self(limit)
+        }
+        fn process<'c>(&mut self, context: &Context, state: &'c mut u32) -> &'c mut u32 {
+            *state += 1;
+            if *state >= self.0 {
+                context.request_exit();
+            }
+            state
+        }
+    }
+
+    fn run_until_exit<Exit>(
+        state: &mut StateFull<u32>,
+        component: &mut ExitAt,
+        mut should_exit: impl FnMut(&mut u32) -> Option<Exit>,
+    ) -> Option<Exit> {
+        for _ in 0..100 {
+            state.run_frame(component, frame(0.1, false));
+            if let Some(exit) = state.exit_reason(&mut should_exit) {
+                return exit;
+            }
+        }
+        panic!("the loop never stopped")
+    }
+
+    fn headless(count: u32) -> StateFull<u32> {
+        StateFull::new_from(count).with_backend(Rc::new(RecordingBackend::new(vec2(100., 100.))))
+    }
+
+    #[test]
+    fn request_exit_stops_the_loop_with_the_final_state() {
+        let mut state = headless(0);
+        let exit = run_until_exit(&mut state, &mut ExitAt(3), |_| None::<()>);
+        assert_eq!(exit, None);
+        assert_eq!(state.into_state(), 3);
+    }
+
+    #[test]
+    fn should_exit_stops_the_loop_with_its_value() {
+        let mut state = headless(0);
+        let exit = run_until_exit(&mut state, &mut ExitAt(10), |count| {
+            (*count == 4).then(|| format!("stopped at {count}"))
+        });
+        assert_eq!(exit.as_deref(), Some("stopped at 4"));
+        assert_eq!(*state.state(), 4);
+    }
+
+    #[test]
+    fn should_exit_wins_when_both_stop_the_same_frame() {
+        let mut state = headless(0);
+        let exit = run_until_exit(&mut state, &mut ExitAt(2), |count| {
+            (*count == 2).then_some(2)
+        });
+        assert_eq!(exit, Some(2));
+    }
+
+    fn context(backend: &Rc<RecordingBackend>, delta: f32) -> Context {
+        Context::new_with_backend(vec2(100., 100.), backend.clone()).with_time(FrameTime::new(
+            delta,
+            delta as f64,
+            1,
+        ))
+    }
+
+    #[test]
+    fn step_runs_process_and_render_once_with_the_given_context() {
+        let (mut state, backend) = state_full(LoopMode::Fixed {
+            step: 0.125,
+            max_steps_per_frame: 8,
+        });
+        state.step(&mut Probe, &context(&backend, 1.));
+        assert_eq!(state.state().deltas, [1.]);
+        assert_eq!(backend.take_draw_calls().len(), 1);
+        // the loop mode is left alone, so no time was stored for later steps
+        state.run_frame(&mut Probe, frame(0.0625, false));
+        assert_eq!(state.state().deltas, [1.]);
+    }
+
+    #[test]
+    fn two_states_can_step_in_the_same_window() {
+        let backend = Rc::new(RecordingBackend::new(vec2(100., 100.)));
+        let mut first = StateFull::new_from(Steps::default());
+        let mut second = StateFull::new_from(Steps::default());
+        let context = context(&backend, 0.5);
+        first.step(&mut Probe, &context);
+        second.step(&mut Probe, &context);
+        second.step(&mut Probe, &context);
+        assert_eq!(first.state().deltas, [0.5]);
+        assert_eq!(second.state().deltas, [0.5, 0.5]);
+        assert_eq!(backend.take_draw_calls().len(), 3);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn replaying_a_recording_reproduces_the_session() {
+        use std::{cell::RefCell, io::Write};
+
+        use crate::components::input::{InputRecorder, InputReplay};
+
+        #[derive(Clone, Default)]
+        struct Shared(Rc<RefCell<Vec<u8>>>);
+
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let frames = vec![
+            frame(0.3125, true),
+            frame(0.0625, false),
+            frame(0.0625, true),
+            frame(0.5, false),
+        ];
+        let recording = Shared::default();
+        let (recorded, _) = state_full(LoopMode::fixed(8.));
+        let mut recorded = recorded
+            .with_recorder(InputRecorder::new(recording.clone()))
+            .with_replay(InputReplay::from_frames(frames));
+        for _ in 0..4 {
+            let frame = recorded.next_input();
+            recorded.run_frame(&mut Probe, frame);
+        }
+
+        let replay = InputReplay::from_reader(recording.0.borrow().as_slice()).unwrap();
+        assert_eq!(replay.frames_left(), 4);
+        let (replayed, _) = state_full(LoopMode::fixed(8.));
+        let mut replayed = replayed.with_replay(replay);
+        for _ in 0..4 {
+            let frame = replayed.next_input();
+            replayed.run_frame(&mut Probe, frame);
+        }
+        assert_eq!(replayed.state().deltas, recorded.state().deltas);
+        assert_eq!(replayed.state().saw_space, recorded.state().saw_space);
+        assert_eq!(
+            replayed.state().saw_space,
+            [true, false, false, true, false, false, false]
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use crate::components::{Component, Context};
+
+/// A list of children where only one of them runs at a time, picked by its index.
+///
+/// Implemented for tuples of up to 16 children, which is what [Switch] uses to store its branches
+pub trait Branches<State> {
+    /// the amount of branches
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn process_branch(&mut self, index: usize, context: &Context, state: &mut State);
+    fn render_branch(&self, index: usize, context: &Context, props: &State);
+    fn ui_branch(
+        &mut self,
+        index: usize,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: &mut State,
+    );
+    fn enter_branch(&mut self, index: usize, context: &Context, state: &mut State);
+    fn exit_branch(&mut self, index: usize, context: &Context, state: &mut State);
+}
+
+macro_rules! impl_branches_for_tuple {
+    ($len:literal; $($comp:ident $index:tt),+) => {
+        impl<State, $($comp: for<'a> Component<&'a State, &'a mut State>,)+> Branches<State>
+            for ($($comp,)+)
+        {
+            fn len(&self) -> usize {
+                $len
+            }
+            fn process_branch(&mut self, index: usize, context: &Context, state: &mut State) {
+                match index {
+                    $($index => {
+                        self.$index.process(context, state);
+                    })+
+                    _ => {}
+                }
+            }
+            fn render_branch(&self, index: usize, context: &Context, props: &State) {
+                match index {
+                    $($index => self.$index.render(context, props),)+
+                    _ => {}
+                }
+            }
+            fn ui_branch(
+                &mut self,
+                index: usize,
+                context: &Context,
+                ui: &mut macroquad::ui::Ui,
+                state: &mut State,
+            ) {
+                match index {
+                    $($index => {
+                        self.$index.ui(context, ui, state);
+                    })+
+                    _ => {}
+                }
+            }
+            fn enter_branch(&mut self, index: usize, context: &Context, state: &mut State) {
+                match index {
+                    $($index => {
+                        self.$index.on_enter(context, state);
+                    })+
+                    _ => {}
+                }
+            }
+            fn exit_branch(&mut self, index: usize, context: &Context, state: &mut State) {
+                match index {
+                    $($index => {
+                        self.$index.on_exit(context, state);
+                    })+
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+impl_branches_for_tuple!(1; Comp 0);
+impl_branches_for_tuple!(2; Comp 0, Comp2 1);
+impl_branches_for_tuple!(3; Comp 0, Comp2 1, Comp3 2);
+impl_branches_for_tuple!(4; Comp 0, Comp2 1, Comp3 2, Comp4 3);
+impl_branches_for_tuple!(5; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4);
+impl_branches_for_tuple!(6; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5);
+impl_branches_for_tuple!(7; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6);
+impl_branches_for_tuple!(8; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7);
+impl_branches_for_tuple!(
+    9; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8
+);
+impl_branches_for_tuple!(
+    10; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9
+);
+impl_branches_for_tuple!(
+    11; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10
+);
+impl_branches_for_tuple!(
+    12; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11
+);
+impl_branches_for_tuple!(
+    13; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12
+);
+impl_branches_for_tuple!(
+    14; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12, Comp14 13
+);
+impl_branches_for_tuple!(
+    15; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12, Comp14 13, Comp15 14
+);
+impl_branches_for_tuple!(
+    16; Comp 0, Comp2 1, Comp3 2, Comp4 3, Comp5 4, Comp6 5, Comp7 6, Comp8 7, Comp9 8, Comp10 9,
+    Comp11 10, Comp12 11, Comp13 12, Comp14 13, Comp15 14, Comp16 15
+);
+
+/// Like [Choice](crate::components::logic::Choice), but picks one out of any amount of branches.
+///
+/// `discriminant` returns the index of the branch that should run, if there is no branch for that index then nothing runs.
+/// The [switch](crate::switch) macro allows the branches to be picked by matching on a value instead.
+pub struct Switch<Discriminant, Children> {
+    discriminant: Discriminant,
+    children: Children,
+    active: Option<usize>,
+}
+
+impl<Discriminant, Children> Switch<Discriminant, Children> {
+    pub fn new<State>(discriminant: Discriminant, children: Children) -> Self
+    where
+        Discriminant: Fn(&State) -> usize,
+        Children: Branches<State>,
+    {
+        Self::instantiate((discriminant, children))
+    }
+    fn switch_to<State>(&mut self, context: &Context, state: &mut State, index: Option<usize>)
+    where
+        Children: Branches<State>,
+    {
+        if self.active == index {
+            return;
+        }
+        if let Some(old) = self.active {
+            self.children.exit_branch(old, context, state);
+        }
+        if let Some(new) = index {
+            self.children.enter_branch(new, context, state);
+        }
+        self.active = index;
+    }
+}
+
+impl<State, Discriminant: Fn(&State) -> usize, Children: Branches<State>>
+    Component<&State, &mut State> for Switch<Discriminant, Children>
+{
+    type Input = (Discriminant, Children);
+
+    fn instantiate((discriminant, children): Self::Input) -> Self {
+        Self {
+            discriminant,
+            children,
+            active: None,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let index = (self.discriminant)(state);
+        self.switch_to(context, state, Some(index));
+        self.children.process_branch(index, context, state);
+        state
+    }
+
+    fn render(&self, context: &Context, props: &State) {
+        self.children
+            .render_branch((self.discriminant)(props), context, props)
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: &'c mut State,
+    ) -> &'c mut State {
+        let index = (self.discriminant)(state);
+        self.switch_to(context, state, Some(index));
+        self.children.ui_branch(index, context, ui, state);
+        state
+    }
+
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        let index = (self.discriminant)(state);
+        self.switch_to(context, state, Some(index));
+        state
+    }
+
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        self.switch_to(context, state, None);
+        state
+    }
+}
+
+/// Creates a [Switch](crate::components::logic::Switch) that picks its branch by matching on a value from the state
+///
+/// ```
+/// use monad_quad::{components::events::Event, switch, testing::TestHarness};
+///
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Screen {
+///     MainMenu,
+///     Playing,
+///     GameOver,
+///     Won,
+/// }
+///
+/// struct MainState {
+///     screen: Screen,
+///     log: Vec<&'static str>,
+/// }
+///
+/// let tree = switch!(|state: &MainState| state.screen, {
+///     Screen::MainMenu => Event::new(|_: &MainState| "main menu", |v, s: &mut MainState| s.log.push(v)),
+///     Screen::Playing => Event::new(|_: &MainState| "playing", |v, s: &mut MainState| s.log.push(v)),
+///     Screen::GameOver | Screen::Won => Event::new(|_: &MainState| "game over", |v, s: &mut MainState| s.log.push(v)),
+/// });
+///
+/// let state = MainState {
+///     screen: Screen::MainMenu,
+///     log: Vec::new(),
+/// };
+/// let mut harness = TestHarness::new(state, tree);
+/// harness.frame(0.1);
+/// harness.state_mut().screen = Screen::Won;
+/// harness.frame(0.1);
+/// assert_eq!(harness.state().log, ["main menu", "game over"]);
+/// ```
+/// The branches are checked in order, if no pattern matches then nothing runs.
+#[macro_export]
+macro_rules! switch {
+    ($discriminant:expr, { $($pattern:pat => $child:expr),+ $(,)? }) => {{
+        let discriminant = $discriminant;
+        $crate::components::logic::Switch::new(
+            move |state: &_| {
+                let value = discriminant(state);
+                $crate::switch!(@index value, 0usize, $($pattern),+)
+            },
+            ($($child,)+),
+        )
+    }};
+    (@index $value:ident, $index:expr, $pattern:pat $(, $rest:pat)*) => {
+        if matches!($value, $pattern) {
+            $index
+        } else {
+            $crate::switch!(@index $value, $index + 1 $(, $rest)*)
+        }
+    };
+    (@index $value:ident, $index:expr) => {
+        $index
+    };
+}
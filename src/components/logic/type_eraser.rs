@@ -33,6 +33,12 @@ impl<RenderState: Clone, ProcessState, Input> Component<RenderState, ProcessStat
     ) -> ProcessState {
         self.child.ui(context, ui, state)
     }
+    fn on_enter(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.child.on_enter(context, state)
+    }
+    fn on_exit(&mut self, context: &Context, state: ProcessState) -> ProcessState {
+        self.child.on_exit(context, state)
+    }
 }
 
 impl<RenderState: Clone, ProcessState, Input> Eraser<RenderState, ProcessState, Input> {
@@ -1,9 +1,11 @@
 mod background;
 mod rectangle;
+mod sprite;
 mod text;
 mod viewport;
 
 pub use background::Background;
 pub use rectangle::{Rectangle, RectangleProps};
+pub use sprite::{Sprite, SpriteProps};
 pub use text::{Text, TextProperties};
 pub use viewport::{ScreenSizeConfig, Viewport};
@@ -0,0 +1,115 @@
+use macroquad::{
+    prelude::{Color, Rect, Vec2, WHITE},
+    texture::{DrawTextureParams, Texture2D},
+};
+
+use crate::{components::Context, Component};
+
+/// Properties to render a [Sprite].
+///
+/// A texture loaded through an [AsyncState](crate::components::asyncs::AsyncState) can be turned into these with [AsyncComp::map_in](crate::components::asyncs::AsyncComp::map_in),
+/// cloning a [Texture2D] only clones the handle, not the texture itself
+#[derive(Clone, Debug)]
+pub struct SpriteProps {
+    pub texture: Texture2D,
+    /// the part of the texture to draw, the whole texture if none
+    pub source: Option<Rect>,
+    /// where the top left corner of the sprite gets drawn
+    pub position: Vec2,
+    /// the size to draw the sprite at, the size of the source if none
+    pub size: Option<Vec2>,
+    /// the rotation in radians
+    pub rotation: f32,
+    /// the point to rotate around, relative to the position. The center of the sprite if none
+    pub pivot: Option<Vec2>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// the color every pixel of the texture gets multiplied with, white leaves it as is
+    pub tint: Color,
+}
+
+impl SpriteProps {
+    /// draws the whole texture at its own size at the top left corner
+    pub fn new(texture: Texture2D) -> Self {
+        Self {
+            texture,
+            source: None,
+            position: Vec2::ZERO,
+            size: None,
+            rotation: 0.,
+            pivot: None,
+            flip_x: false,
+            flip_y: false,
+            tint: WHITE,
+        }
+    }
+    pub fn with_source(mut self, source: Rect) -> Self {
+        self.source = Some(source);
+        self
+    }
+    pub fn with_position(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = Some(size);
+        self
+    }
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+    pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+}
+
+/// Draws (a part of) a texture
+pub struct Sprite;
+
+impl Sprite {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component<&SpriteProps, &mut SpriteProps> for Sprite {
+    type Input = ();
+
+    fn instantiate(_: Self::Input) -> Self {
+        Self::new()
+    }
+
+    fn render(&self, context: &Context, props: &SpriteProps) {
+        context.draw_texture(
+            &props.texture,
+            props.position.x,
+            props.position.y,
+            props.tint,
+            &DrawTextureParams {
+                dest_size: props.size,
+                source: props.source,
+                rotation: props.rotation,
+                flip_x: props.flip_x,
+                flip_y: props.flip_y,
+                pivot: props.pivot.map(|v| props.position + v),
+            },
+        )
+    }
+}
@@ -0,0 +1,5 @@
+mod router;
+mod stack;
+
+pub use router::Router;
+pub use stack::{SceneStack, SlideDirection, Transition, TransitionKind};
@@ -0,0 +1,481 @@
+use macroquad::prelude::{vec2, Vec2};
+
+use crate::components::{logic::Branches, Component, Context};
+
+use super::{SceneStack, SlideDirection, TransitionKind};
+
+/// Shows the scene on top of a [SceneStack], playing the transition whenever the stack changes.
+///
+/// `scene_index` picks the child for a scene, the same way the discriminant of [Switch](crate::components::logic::Switch) does.
+/// Only the top scene gets processed and gets its ui drawn, but while a transition runs both the old and the new scene get rendered.
+/// The new scene gets [Component::on_enter] as soon as it is on top, the old one gets [Component::on_exit] once the transition is done.
+pub struct Router<GetStack, GetStackMut, SceneIndex, Children> {
+    get_stack: GetStack,
+    get_stack_mut: GetStackMut,
+    scene_index: SceneIndex,
+    children: Children,
+    current: Option<usize>,
+    leaving: Option<usize>,
+}
+
+/// what needs to be drawn this frame, in the order it needs to be drawn in
+struct Layer {
+    index: usize,
+    offset: Vec2,
+    opacity: f32,
+    /// true for the scene on top of the stack, the only one that gets to draw its ui
+    top: bool,
+}
+
+impl<GetStack, GetStackMut, SceneIndex, Children>
+    Router<GetStack, GetStackMut, SceneIndex, Children>
+{
+    pub fn new<State, Scene>(
+        get_stack: GetStack,
+        get_stack_mut: GetStackMut,
+        scene_index: SceneIndex,
+        children: Children,
+    ) -> Self
+    where
+        GetStack: Fn(&State) -> &SceneStack<Scene>,
+        GetStackMut: Fn(&mut State) -> &mut SceneStack<Scene>,
+        SceneIndex: Fn(&Scene) -> usize,
+        Children: Branches<State>,
+    {
+        Self::instantiate((get_stack, get_stack_mut, scene_index, children))
+    }
+    fn sync_scenes<State, Scene>(&mut self, context: &Context, state: &mut State)
+    where
+        GetStack: Fn(&State) -> &SceneStack<Scene>,
+        SceneIndex: Fn(&Scene) -> usize,
+        Children: Branches<State>,
+    {
+        let stack = (self.get_stack)(state);
+        let top = stack.top().map(&self.scene_index);
+        let transitioning = stack
+            .running_transition()
+            .map(|v| v.transition.kind != TransitionKind::None)
+            .unwrap_or(false);
+        if !transitioning {
+            if let Some(leaving) = self.leaving.take() {
+                self.children.exit_branch(leaving, context, state);
+            }
+        }
+        if top == self.current {
+            return;
+        }
+        if let Some(old) = self.current {
+            if transitioning {
+                // the old scene is still visible until the transition is done
+                if let Some(leaving) = self.leaving.replace(old) {
+                    self.children.exit_branch(leaving, context, state);
+                }
+            } else {
+                self.children.exit_branch(old, context, state);
+            }
+        }
+        if let Some(new) = top {
+            self.children.enter_branch(new, context, state);
+        }
+        self.current = top;
+    }
+    fn layers<State, Scene>(&self, context: &Context, state: &State) -> Vec<Layer>
+    where
+        GetStack: Fn(&State) -> &SceneStack<Scene>,
+        SceneIndex: Fn(&Scene) -> usize,
+    {
+        let stack = (self.get_stack)(state);
+        let Some(incoming) = stack.top().map(&self.scene_index) else {
+            return Vec::new();
+        };
+        let layer = |index, offset, opacity| Layer {
+            index,
+            offset,
+            opacity,
+            top: false,
+        };
+        let top = |index, offset, opacity| Layer {
+            top: true,
+            ..layer(index, offset, opacity)
+        };
+        let transition = match stack.running_transition() {
+            Some(x) if x.transition.kind != TransitionKind::None => x,
+            _ => return vec![top(incoming, Vec2::ZERO, 1.)],
+        };
+        let Some(outgoing) = transition.from.as_ref().map(&self.scene_index) else {
+            return vec![top(incoming, Vec2::ZERO, 1.)];
+        };
+        let progress = transition.progress();
+        match transition.transition.kind {
+            TransitionKind::None => vec![top(incoming, Vec2::ZERO, 1.)],
+            TransitionKind::Fade if transition.popping => vec![
+                top(incoming, Vec2::ZERO, 1.),
+                layer(outgoing, Vec2::ZERO, 1. - progress),
+            ],
+            TransitionKind::Fade => vec![
+                layer(outgoing, Vec2::ZERO, 1.),
+                top(incoming, Vec2::ZERO, progress),
+            ],
+            TransitionKind::Slide(direction) => {
+                let size = context.viewport_size();
+                let from = match direction {
+                    SlideDirection::Left => vec2(-size.x, 0.),
+                    SlideDirection::Right => vec2(size.x, 0.),
+                    SlideDirection::Top => vec2(0., -size.y),
+                    SlideDirection::Bottom => vec2(0., size.y),
+                };
+                if transition.popping {
+                    // the popped scene leaves the way it came in
+                    vec![
+                        top(incoming, -from * (1. - progress), 1.),
+                        layer(outgoing, from * progress, 1.),
+                    ]
+                } else {
+                    vec![
+                        layer(outgoing, -from * progress, 1.),
+                        top(incoming, from * (1. - progress), 1.),
+                    ]
+                }
+            }
+        }
+    }
+    /// the layer of the scene on top of the stack, the only one that gets its ui drawn
+    fn ui_layer<State, Scene>(&self, context: &Context, state: &State) -> Option<Layer>
+    where
+        GetStack: Fn(&State) -> &SceneStack<Scene>,
+        SceneIndex: Fn(&Scene) -> usize,
+    {
+        self.layers(context, state).into_iter().find(|v| v.top)
+    }
+}
+
+impl Layer {
+    fn context(&self, context: &Context) -> Context {
+        context
+            .clone()
+            .with_offset(self.offset)
+            .with_opacity(self.opacity)
+    }
+}
+
+impl<
+        State,
+        Scene,
+        GetStack: Fn(&State) -> &SceneStack<Scene>,
+        GetStackMut: Fn(&mut State) -> &mut SceneStack<Scene>,
+        SceneIndex: Fn(&Scene) -> usize,
+        Children: Branches<State>,
+    > Component<&State, &mut State> for Router<GetStack, GetStackMut, SceneIndex, Children>
+{
+    type Input = (GetStack, GetStackMut, SceneIndex, Children);
+
+    fn instantiate((get_stack, get_stack_mut, scene_index, children): Self::Input) -> Self {
+        Self {
+            get_stack,
+            get_stack_mut,
+            scene_index,
+            children,
+            current: None,
+            leaving: None,
+        }
+    }
+
+    fn process<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        (self.get_stack_mut)(state).advance_transition(context.get_delta());
+        self.sync_scenes(context, state);
+        if let Some(current) = self.current {
+            self.children.process_branch(current, context, state);
+        }
+        state
+    }
+
+    fn render(&self, context: &Context, props: &State) {
+        for layer in self.layers(context, props) {
+            self.children
+                .render_branch(layer.index, &layer.context(context), props);
+        }
+    }
+
+    fn ui<'c>(
+        &mut self,
+        context: &Context,
+        ui: &mut macroquad::ui::Ui,
+        state: &'c mut State,
+    ) -> &'c mut State {
+        self.sync_scenes(context, state);
+        // the scene that is leaving is only there to be looked at, so it shouldn't react to the mouse anymore
+        if let Some(layer) = self.ui_layer(context, state) {
+            self.children
+                .ui_branch(layer.index, &layer.context(context), ui, state);
+        }
+        state
+    }
+
+    fn on_enter<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        self.sync_scenes(context, state);
+        state
+    }
+
+    fn on_exit<'c>(&mut self, context: &Context, state: &'c mut State) -> &'c mut State {
+        if let Some(leaving) = self.leaving.take() {
+            self.children.exit_branch(leaving, context, state);
+        }
+        if let Some(current) = self.current.take() {
+            self.children.exit_branch(current, context, state);
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use macroquad::prelude::{vec2, WHITE};
+
+    use super::Router;
+    use crate::{
+        components::{
+            animation::TweenKind,
+            backend::{DrawCall, RecordingBackend},
+            scenes::{SceneStack, SlideDirection, Transition},
+            Component, Context,
+        },
+        testing::TestHarness,
+    };
+
+    struct State {
+        stack: SceneStack<usize>,
+        events: Vec<String>,
+    }
+
+    impl State {
+        fn new() -> Self {
+            Self {
+                stack: SceneStack::new(0),
+                events: Vec::new(),
+            }
+        }
+    }
+
+    /// logs its lifecycle and draws a rectangle at `x = index * 10`, so the draw calls show which scenes got rendered
+    struct Probe(usize);
+
+    impl Component<&State, &mut State> for Probe {
+        type Input = usize;
+
+        fn instantiate(index: Self::Input) -> Self {
+            Self(index)
+        }
+        fn process<'c>(&mut self, _: &Context, state: &'c mut State) -> &'c mut State {
+            state.events.push(format!("{} process", self.0));
+            state
+        }
+        fn render(&self, context: &Context, _: &State) {
+            context.draw_rectangle(self.0 as f32 * 10., 0., 1., 1., WHITE)
+        }
+        fn on_enter<'c>(&mut self, _: &Context, state: &'c mut State) -> &'c mut State {
+            state.events.push(format!("{} enter", self.0));
+            state
+        }
+        fn on_exit<'c>(&mut self, _: &Context, state: &'c mut State) -> &'c mut State {
+            state.events.push(format!("{} exit", self.0));
+            state
+        }
+    }
+
+    fn stack(state: &State) -> &SceneStack<usize> {
+        &state.stack
+    }
+
+    fn stack_mut(state: &mut State) -> &mut SceneStack<usize> {
+        &mut state.stack
+    }
+
+    fn index(scene: &usize) -> usize {
+        *scene
+    }
+
+    type Probes = (Probe, Probe, Probe);
+    type TestRouter = Router<
+        fn(&State) -> &SceneStack<usize>,
+        fn(&mut State) -> &mut SceneStack<usize>,
+        fn(&usize) -> usize,
+        Probes,
+    >;
+
+    fn router() -> TestRouter {
+        Router::new(
+            stack as fn(&_) -> &_,
+            stack_mut as fn(&mut _) -> &mut _,
+            index as fn(&_) -> _,
+            (Probe(0), Probe(1), Probe(2)),
+        )
+    }
+
+    fn harness() -> TestHarness<State, TestRouter> {
+        TestHarness::new(State::new(), router())
+    }
+
+    fn take_events(harness: &mut TestHarness<State, TestRouter>) -> Vec<String> {
+        std::mem::take(&mut harness.state_mut().events)
+    }
+
+    fn rendered(harness: &TestHarness<State, TestRouter>) -> Vec<(f32, f32)> {
+        harness
+            .draw_calls()
+            .iter()
+            .filter_map(|v| match v {
+                DrawCall::Rectangle { x, color, .. } => Some((*x, color.a)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn fade() -> Transition {
+        Transition::fade(1., TweenKind::Linear)
+    }
+
+    #[test]
+    fn stack_push_pop_and_replace() {
+        let mut stack = SceneStack::new(0);
+        assert_eq!(stack.pop(Transition::none()), None);
+        assert!(!stack.is_transitioning());
+        assert_eq!(stack.scenes(), &[0]);
+
+        stack.push(1, fade());
+        assert_eq!(stack.top(), Some(&1));
+        assert!(stack.is_transitioning());
+        assert_eq!(stack.replace(2, Transition::none()), Some(1));
+        assert_eq!(stack.scenes(), &[0, 2]);
+        assert_eq!(stack.pop(Transition::none()), Some(2));
+        assert_eq!(stack.scenes(), &[0]);
+        assert_eq!(stack.pop(Transition::none()), None);
+        assert_eq!(stack.top(), Some(&0));
+    }
+
+    #[test]
+    fn scenes_without_a_transition_switch_right_away() {
+        let mut harness = harness();
+        harness.frame(0.1);
+        assert_eq!(take_events(&mut harness), ["0 enter", "0 process"]);
+
+        harness.state_mut().stack.push(1, Transition::none());
+        harness.frame(0.1);
+        assert_eq!(
+            take_events(&mut harness),
+            ["0 exit", "1 enter", "1 process"]
+        );
+        assert_eq!(rendered(&harness), [(10., 1.)]);
+
+        harness.state_mut().stack.replace(2, Transition::none());
+        harness.frame(0.1);
+        assert_eq!(
+            take_events(&mut harness),
+            ["1 exit", "2 enter", "2 process"]
+        );
+
+        harness.state_mut().stack.pop(Transition::none());
+        harness.frame(0.1);
+        assert_eq!(
+            take_events(&mut harness),
+            ["2 exit", "0 enter", "0 process"]
+        );
+
+        // the last scene stays
+        harness.state_mut().stack.pop(Transition::none());
+        harness.frame(0.1);
+        assert_eq!(take_events(&mut harness), ["0 process"]);
+    }
+
+    #[test]
+    fn the_old_scene_exits_once_the_transition_is_done() {
+        let mut harness = harness();
+        harness.frame(0.1);
+        take_events(&mut harness);
+
+        harness.state_mut().stack.push(1, fade());
+        harness.frame(0.25);
+        assert_eq!(take_events(&mut harness), ["1 enter", "1 process"]);
+        // the old scene stays visible below the new one, which fades in
+        assert_eq!(rendered(&harness), [(0., 1.), (10., 0.25)]);
+
+        harness.frame(0.5);
+        assert_eq!(take_events(&mut harness), ["1 process"]);
+        harness.frame(0.5);
+        assert_eq!(take_events(&mut harness), ["0 exit", "1 process"]);
+        assert_eq!(rendered(&harness), [(10., 1.)]);
+    }
+
+    #[test]
+    fn pushing_during_a_transition_exits_the_scene_that_was_still_leaving() {
+        let mut harness = harness();
+        harness.frame(0.1);
+        harness.state_mut().stack.push(1, fade());
+        harness.frame(0.25);
+        take_events(&mut harness);
+
+        harness.state_mut().stack.push(2, fade());
+        harness.frame(0.25);
+        assert_eq!(
+            take_events(&mut harness),
+            ["0 exit", "2 enter", "2 process"]
+        );
+        assert_eq!(rendered(&harness), [(10., 1.), (20., 0.25)]);
+
+        harness.frames(2, 0.5);
+        assert_eq!(
+            take_events(&mut harness),
+            ["2 process", "1 exit", "2 process"]
+        );
+    }
+
+    #[test]
+    fn popping_slides_the_old_scene_out() {
+        let mut harness = harness();
+        harness.frame(0.1);
+        harness.state_mut().stack.push(1, Transition::none());
+        harness.frame(0.1);
+        harness.state_mut().stack.pop(Transition::slide(
+            SlideDirection::Right,
+            1.,
+            TweenKind::Linear,
+        ));
+        harness.frame(0.25);
+        // the scene below comes back from the left while the popped one leaves to the right
+        let width = 1920.;
+        assert_eq!(
+            rendered(&harness),
+            [(-width * 0.75, 1.), (10. + width * 0.25, 1.)]
+        );
+    }
+
+    #[test]
+    fn only_the_top_scene_gets_its_ui_drawn() {
+        let context = Context::new_with_backend(
+            vec2(100., 100.),
+            Rc::new(RecordingBackend::new(vec2(100., 100.))),
+        );
+        let router = router();
+        let mut state = State::new();
+        state.stack.push(1, fade());
+        let layers = router.layers(&context, &state);
+        assert_eq!(layers.len(), 2);
+        let ui_layer = router.ui_layer(&context, &state).unwrap();
+        assert_eq!(ui_layer.index, 1);
+
+        state.stack.pop(fade());
+        let layers = router.layers(&context, &state);
+        assert_eq!(layers.iter().map(|v| v.index).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(router.ui_layer(&context, &state).unwrap().index, 0);
+
+        let mut state = State::new();
+        state.stack.push(
+            1,
+            Transition::slide(SlideDirection::Left, 1., TweenKind::Linear),
+        );
+        let ui_layer = router.ui_layer(&context, &state).unwrap();
+        assert_eq!(ui_layer.index, 1);
+        assert_eq!(ui_layer.offset, vec2(-100., 0.));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::components::animation::TweenKind;
+
+/// The side of the screen a new scene slides in from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlideDirection {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// How a [Router](super::Router) moves from one scene to the next.
+///
+/// The offset of a slide also applies to the ui widgets and the mouse location, but macroquad's ui can't be drawn transparent.
+/// So while fading the ui of the new scene shows up at full opacity right away
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// the new scene shows up right away
+    None,
+    /// the new scene fades in on top of the old one, or the old one fades out when it gets popped
+    Fade,
+    /// the new scene pushes the old one out of the way
+    Slide(SlideDirection),
+}
+
+/// How to go from one scene to the next
+#[derive(Clone, Debug)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub tween_kind: TweenKind,
+    /// how long the transition takes in seconds
+    pub duration: f32,
+}
+
+impl Transition {
+    pub fn none() -> Self {
+        Self {
+            kind: TransitionKind::None,
+            tween_kind: TweenKind::Linear,
+            duration: 0.,
+        }
+    }
+    pub fn fade(duration: f32, tween_kind: TweenKind) -> Self {
+        Self {
+            kind: TransitionKind::Fade,
+            tween_kind,
+            duration,
+        }
+    }
+    pub fn slide(direction: SlideDirection, duration: f32, tween_kind: TweenKind) -> Self {
+        Self {
+            kind: TransitionKind::Slide(direction),
+            tween_kind,
+            duration,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct RunningTransition<Scene> {
+    pub(crate) from: Option<Scene>,
+    pub(crate) transition: Transition,
+    pub(crate) at: f32,
+    pub(crate) popping: bool,
+}
+
+impl<Scene> RunningTransition<Scene> {
+    /// how far along the transition is, with the tween applied
+    pub(crate) fn progress(&self) -> f32 {
+        self.transition.tween_kind.ease(self.at.clamp(0., 1.))
+    }
+}
+
+/// A stack of scenes that lives in the state, the scene on top is the one that is shown.
+///
+/// Changing the stack starts a transition from the old top scene to the new one, which a [Router](super::Router) plays.
+#[derive(Clone, Debug)]
+pub struct SceneStack<Scene> {
+    scenes: Vec<Scene>,
+    transition: Option<RunningTransition<Scene>>,
+}
+
+impl<Scene: Clone> SceneStack<Scene> {
+    /// creates a stack that starts out with just the given scene
+    pub fn new(first: Scene) -> Self {
+        Self {
+            scenes: vec![first],
+            transition: None,
+        }
+    }
+    /// puts a new scene on top of the stack
+    pub fn push(&mut self, scene: Scene, transition: Transition) {
+        self.start_transition(transition, false);
+        self.scenes.push(scene);
+    }
+    /// removes the top scene and goes back to the one below it.
+    ///
+    /// The last scene never gets popped, in that case nothing happens and None is returned
+    pub fn pop(&mut self, transition: Transition) -> Option<Scene> {
+        if self.scenes.len() <= 1 {
+            return None;
+        }
+        self.start_transition(transition, true);
+        self.scenes.pop()
+    }
+    /// swaps the top scene for a new one, returning the old one
+    pub fn replace(&mut self, scene: Scene, transition: Transition) -> Option<Scene> {
+        self.start_transition(transition, false);
+        let old = self.scenes.pop();
+        self.scenes.push(scene);
+        old
+    }
+    fn start_transition(&mut self, transition: Transition, popping: bool) {
+        self.transition = Some(RunningTransition {
+            from: self.scenes.last().cloned(),
+            transition,
+            at: 0.,
+            popping,
+        });
+    }
+}
+
+impl<Scene> SceneStack<Scene> {
+    /// the scene that is currently shown, or being transitioned to
+    pub fn top(&self) -> Option<&Scene> {
+        self.scenes.last()
+    }
+    /// every scene on the stack, the last one is on top
+    pub fn scenes(&self) -> &[Scene] {
+        &self.scenes
+    }
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+    /// how far along the current transition is, between 0 and 1 and without the tween applied
+    pub fn transition_time(&self) -> Option<f32> {
+        self.transition.as_ref().map(|v| v.at)
+    }
+    pub(crate) fn running_transition(&self) -> Option<&RunningTransition<Scene>> {
+        self.transition.as_ref()
+    }
+    /// moves the current transition forward by `delta` seconds
+    pub(crate) fn advance_transition(&mut self, delta: f32) {
+        if let Some(transition) = &mut self.transition {
+            if transition.transition.duration <= 0. {
+                transition.at = 1.
+            } else {
+                transition.at += delta / transition.transition.duration;
+            }
+            if transition.at >= 1. {
+                self.transition = None;
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::{borrow::Cow, cell::RefCell, rc::Rc};
 
-use macroquad::{miniquad::gl::glFlush, prelude::Rect, window::get_internal_gl};
+use macroquad::prelude::Rect;
 use macroquad_tiled::Map;
 
 use crate::Component;
@@ -24,12 +24,7 @@ impl<'a> Component<&TiledProperties<'a>, &mut TiledProperties<'a>> for Tiled {
     {
         Self
     }
-    fn render(&self, _: &super::Context, props: &TiledProperties<'a>) {
-        props
-            .map
-            .borrow()
-            .draw_tiles(&props.layer, props.dest, props.source);
-        let mut gl = unsafe { get_internal_gl() };
-        gl.flush()
+    fn render(&self, context: &super::Context, props: &TiledProperties<'a>) {
+        context.draw_tiles(&props.map.borrow(), &props.layer, props.dest, props.source)
     }
 }
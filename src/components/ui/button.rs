@@ -44,7 +44,7 @@ impl<'a, T, OnClick: Fn(&mut ButtonProperties<'a, T>)>
 
     fn ui<'c>(
         &mut self,
-        _: &Context,
+        context: &Context,
         ui: &mut macroquad::ui::Ui,
         state: &'c mut ButtonProperties<'a, T>,
     ) -> &'c mut ButtonProperties<'a, T> {
@@ -53,7 +53,7 @@ impl<'a, T, OnClick: Fn(&mut ButtonProperties<'a, T>)>
             UiContent::Texture(x) => UiContent::Texture(x.clone()),
         };
         let x = widgets::Button::new(x)
-            .position(state.position)
+            .position(state.position + context.offset())
             .selected(state.selected)
             .size(state.size)
             .ui(ui);
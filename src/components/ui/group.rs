@@ -48,4 +48,22 @@ impl<T, Child: for<'z> Component<&'z T, &'z mut T>>
         });
         state
     }
+
+    fn on_enter<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut GroupProperties<T>,
+    ) -> &'c mut GroupProperties<T> {
+        self.child.on_enter(context, &mut state.extra_data);
+        state
+    }
+
+    fn on_exit<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut GroupProperties<T>,
+    ) -> &'c mut GroupProperties<T> {
+        self.child.on_exit(context, &mut state.extra_data);
+        state
+    }
 }
@@ -21,11 +21,11 @@ impl Component<&LabelProperties, &mut LabelProperties> for Label {
 
     fn ui<'c>(
         &mut self,
-        _: &Context,
+        context: &Context,
         ui: &mut macroquad::ui::Ui,
         state: &'c mut LabelProperties,
     ) -> &'c mut LabelProperties {
-        ui.label(state.location, &state.label);
+        ui.label(state.location + context.offset(), &state.label);
         state
     }
 }
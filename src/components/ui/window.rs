@@ -47,17 +47,38 @@ impl<T, Child: for<'z> Component<&'z T, &'z mut T>>
         ui: &mut macroquad::ui::Ui,
         state: &'c mut WindowProperties<T>,
     ) -> &'c mut WindowProperties<T> {
-        let mut window = widgets::Window::new(self.id, state.location, state.size)
-            .titlebar(state.title_bar)
-            .movable(state.moveable)
-            .close_button(state.close_button);
+        let mut window =
+            widgets::Window::new(self.id, state.location + context.offset(), state.size)
+                .titlebar(state.title_bar)
+                .movable(state.moveable)
+                .close_button(state.close_button);
         if let Some(label) = &state.label {
             window = window.label(label);
         }
 
+        // the content of the window is positioned relative to the window, which already got moved
+        let child_context = context.clone().without_offset();
         window.ui(ui, |ui| {
-            self.child.ui(context, ui, &mut state.extra_data);
+            self.child.ui(&child_context, ui, &mut state.extra_data);
         });
         state
     }
+
+    fn on_enter<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut WindowProperties<T>,
+    ) -> &'c mut WindowProperties<T> {
+        self.child.on_enter(context, &mut state.extra_data);
+        state
+    }
+
+    fn on_exit<'c>(
+        &mut self,
+        context: &Context,
+        state: &'c mut WindowProperties<T>,
+    ) -> &'c mut WindowProperties<T> {
+        self.child.on_exit(context, &mut state.extra_data);
+        state
+    }
 }
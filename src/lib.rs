@@ -1,2 +1,3 @@
 pub mod components;
+pub mod testing;
 pub use components::Component;
@@ -0,0 +1,235 @@
+use std::rc::Rc;
+
+use macroquad::prelude::{vec2, Vec2};
+
+use crate::{
+    components::{
+        backend::{DrawCall, RecordingBackend},
+        input::{InputSnapshot, RecordedFrame},
+        logic::StateFull,
+        Context, FrameTime,
+    },
+    Component,
+};
+
+/// Runs a component against a state, one scripted frame at a time.
+///
+/// Every frame runs process and render with the given delta and input, the draw calls end up in a [RecordingBackend].
+/// The ui pass gets skipped, as macroquad's ui needs a window.
+pub struct TestHarness<T, Comp> {
+    state: StateFull<T>,
+    component: Comp,
+    backend: Rc<RecordingBackend>,
+    context: Context,
+    time: FrameTime,
+    input: InputSnapshot,
+    draw_calls: Vec<DrawCall>,
+}
+
+impl<T, Comp: for<'a> Component<&'a T, &'a mut T>> TestHarness<T, Comp> {
+    /// creates a harness for an already instantiated component, with a window of 1920x1080
+    pub fn new(state: T, component: Comp) -> Self {
+        Self::new_with_window_size(state, component, vec2(1920., 1080.))
+    }
+    /// creates a harness that pretends to have a window of the given size
+    pub fn new_with_window_size(state: T, component: Comp, window_size: Vec2) -> Self {
+        let backend = Rc::new(RecordingBackend::new(window_size));
+        Self {
+            state: StateFull::new_from(state),
+            component,
+            context: Context::new_with_backend(window_size, backend.clone()),
+            backend,
+            time: FrameTime::default(),
+            input: InputSnapshot::new(),
+            draw_calls: Vec::new(),
+        }
+    }
+    /// runs a single frame that took `delta` seconds, where the input stays the same as the frame before.
+    ///
+    /// Keys and buttons that are held down stay held down, everything else gets cleared
+    pub fn frame(&mut self, delta: f32) -> &mut Self {
+        self.frame_with(delta, |_| {})
+    }
+    /// runs a single frame that took `delta` seconds, after `change_input` got to change the input of this frame
+    pub fn frame_with(
+        &mut self,
+        delta: f32,
+        change_input: impl FnOnce(&mut InputSnapshot),
+    ) -> &mut Self {
+        let mut input = self.input.next_frame();
+        change_input(&mut input);
+        self.run_frame(RecordedFrame {
+            delta,
+            window_size: None,
+            input,
+        })
+    }
+    /// runs `count` frames that each took `delta` seconds, without changing the input
+    pub fn frames(&mut self, count: usize, delta: f32) -> &mut Self {
+        for _ in 0..count {
+            self.frame(delta);
+        }
+        self
+    }
+    /// runs a frame for every given frame, using its delta and input as is
+    pub fn run_frames(&mut self, frames: impl IntoIterator<Item = RecordedFrame>) -> &mut Self {
+        for frame in frames {
+            self.run_frame(frame);
+        }
+        self
+    }
+    /// runs a single frame with the exact delta and input that are given
+    pub fn run_frame(&mut self, frame: RecordedFrame) -> &mut Self {
+        self.time = self.time.next(frame.delta);
+        let context = match frame.window_size {
+            Some(window_size) => self.context.clone().with_window_size(window_size),
+            None => self.context.clone(),
+        };
+        let context = context
+            .with_time(self.time)
+            .with_input(Rc::new(frame.input.clone()));
+        self.input = frame.input;
+        self.backend.take_draw_calls();
+        self.state.step(&mut self.component, &context);
+        self.draw_calls = self.backend.take_draw_calls();
+        self
+    }
+}
+
+impl<T, Comp> TestHarness<T, Comp> {
+    pub fn state(&self) -> &T {
+        self.state.state()
+    }
+    /// allows the state to be changed in between frames
+    pub fn state_mut(&mut self) -> &mut T {
+        self.state.state_mut()
+    }
+    pub fn component(&self) -> &Comp {
+        &self.component
+    }
+    /// the time as seen by the last frame that ran
+    pub fn time(&self) -> FrameTime {
+        self.time
+    }
+    /// the draw calls made during the last frame that ran
+    pub fn draw_calls(&self) -> &[DrawCall] {
+        &self.draw_calls
+    }
+    /// true once a component called [Context::request_exit]
+    pub fn is_exit_requested(&self) -> bool {
+        self.context.is_exit_requested()
+    }
+    /// stops the harness and gives the state back
+    pub fn into_state(self) -> T {
+        self.state.into_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::{vec2, KeyCode, Vec2, RED};
+
+    use super::TestHarness;
+    use crate::components::{
+        backend::DrawCall,
+        events::ContextEvent,
+        render::{Rectangle, RectangleProps},
+        Context, FrameTime,
+    };
+
+    #[test]
+    fn held_keys_carry_over_to_the_next_frame() {
+        let mut harness = TestHarness::new(
+            Vec::new(),
+            ContextEvent::new(
+                |context: &Context, _: &Vec<(bool, bool)>| {
+                    let input = context.input();
+                    (
+                        input.is_key_down(KeyCode::A),
+                        input.is_key_pressed(KeyCode::A),
+                    )
+                },
+                |seen, state: &mut Vec<(bool, bool)>| state.push(seen),
+            ),
+        );
+        harness
+            .frame_with(0.1, |input| {
+                input.press_key(KeyCode::A);
+            })
+            .frame(0.1)
+            .frame_with(0.1, |input| {
+                input.release_key(KeyCode::A);
+            })
+            .frame(0.1);
+        assert_eq!(
+            harness.state(),
+            &vec![(true, true), (true, false), (false, false), (false, false)]
+        );
+    }
+
+    #[test]
+    fn mouse_position_carries_over_to_the_next_frame() {
+        let mut harness = TestHarness::new(
+            Vec2::ZERO,
+            ContextEvent::new(
+                |context: &Context, _: &Vec2| context.input().mouse_position,
+                |position, state: &mut Vec2| *state = position,
+            ),
+        );
+        harness
+            .frame_with(0.1, |input| {
+                input.move_mouse(vec2(12., 34.));
+            })
+            .frame(0.1);
+        assert_eq!(harness.state(), &vec2(12., 34.));
+    }
+
+    #[test]
+    fn time_moves_forward_by_the_given_delta() {
+        let mut harness = TestHarness::new(
+            Vec::new(),
+            ContextEvent::new(
+                |context: &Context, _: &Vec<FrameTime>| context.time(),
+                |time, state: &mut Vec<FrameTime>| state.push(time),
+            ),
+        );
+        harness.frames(2, 0.5).frame(0.25);
+        assert_eq!(
+            harness.state(),
+            &vec![
+                FrameTime::new(0.5, 0.5, 1),
+                FrameTime::new(0.5, 1., 2),
+                FrameTime::new(0.25, 1.25, 3),
+            ]
+        );
+        assert_eq!(harness.time(), FrameTime::new(0.25, 1.25, 3));
+    }
+
+    #[test]
+    fn records_the_draw_calls_of_the_last_frame() {
+        let mut harness = TestHarness::new(
+            RectangleProps {
+                size: vec2(10., 20.),
+                color: RED,
+                location: vec2(1., 2.),
+            },
+            Rectangle::new(),
+        );
+        harness.frame(0.1);
+        let expected = [DrawCall::Rectangle {
+            x: 1.,
+            y: 2.,
+            w: 10.,
+            h: 20.,
+            color: RED,
+        }];
+        assert_eq!(harness.draw_calls(), &expected);
+        harness.state_mut().location = vec2(5., 6.);
+        harness.frame(0.1);
+        assert_eq!(harness.draw_calls().len(), 1);
+        assert!(matches!(
+            harness.draw_calls()[0],
+            DrawCall::Rectangle { x, y, .. } if x == 5. && y == 6.
+        ));
+    }
+}
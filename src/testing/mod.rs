@@ -0,0 +1,5 @@
+//! Helpers to run a scene tree without a window, so its behaviour can be checked in a normal `cargo test`.
+mod harness;
+
+pub use crate::components::backend::DrawCall;
+pub use harness::TestHarness;